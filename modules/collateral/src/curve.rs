@@ -0,0 +1,88 @@
+// curve.rs
+//! Bond curves scale a base bond continuously with how dilutive an action is, so that there are
+//! no cliffs for applicants to game by asking for exactly the bank's ratio
+use super::*;
+
+/// Millionths in a multiplier of one
+pub const ONE: u128 = 1_000_000;
+
+/// Multiplier of a base bond as a function of the distance between an action's shares per
+/// capital and the bank's
+pub trait BondCurve {
+    /// Multiplier, in millionths, for an action whose shares per capital is `relative`
+    /// millionths of the bank's (see `relative_shares_per_capital`)
+    /// - `relative` is `ONE` at the bank's ratio and zero for actions that issue no shares, so
+    /// the distance from the bank's ratio is `relative - ONE`
+    /// - must be continuous and non-decreasing
+    fn multiplier(relative: u128) -> u128;
+}
+
+/// 1x for actions that issue no shares, 2x at the bank's ratio and another 1x for every 100%
+/// further from it
+pub struct Linear;
+
+impl BondCurve for Linear {
+    fn multiplier(relative: u128) -> u128 {
+        ONE.saturating_add(relative)
+    }
+}
+
+/// Doubles with every 100% of distance: 1x for actions that issue no shares, 2x at the bank's
+/// ratio and 4x at twice the bank's ratio
+pub struct Exponential;
+
+impl BondCurve for Exponential {
+    fn multiplier(relative: u128) -> u128 {
+        let whole = relative / ONE;
+        if whole >= 100 {
+            return u128::max_value();
+        }
+        let fraction = relative % ONE;
+        // 2^fraction ~= 1 + fraction * (0.6565 + 0.3435 * fraction), exact at 0 and 1 so the
+        // curve stays continuous across whole multiples of the bank's ratio
+        let fractional = ONE + fraction * (656_500 + 343_500 * fraction / ONE) / ONE;
+        fractional.saturating_mul(1u128 << whole)
+    }
+}
+
+/// Joins the 1x, 2x and 4x of the step function at zero, the bank's ratio and twice the bank's
+/// ratio with straight lines, and stays at 4x beyond that
+pub struct PiecewiseLinear;
+
+impl BondCurve for PiecewiseLinear {
+    fn multiplier(relative: u128) -> u128 {
+        if relative <= ONE {
+            ONE + relative
+        } else {
+            relative
+                .saturating_sub(ONE)
+                .saturating_mul(2)
+                .saturating_add(2 * ONE)
+                .min(4 * ONE)
+        }
+    }
+}
+
+/// Shares per capital of `new` in millionths of the shares per capital of `existing`
+/// - saturates if `existing` has no shares per capital or `new` has no capital per share
+/// - amounts above 2^40 are rounded to their 40 most significant bits so the products can't
+/// overflow, which only loses precision far below a millionth
+pub fn relative_shares_per_capital(new: &ConversionRate, existing: &ConversionRate) -> u128 {
+    let (new_capital, new_shares) = significant(new.to_rational());
+    let (existing_capital, existing_shares) = significant(existing.to_rational());
+    let denominator = new_capital * existing_shares;
+    if denominator == 0 {
+        return u128::max_value();
+    }
+    new_shares * existing_capital * ONE / denominator
+}
+
+/// Bits kept of the larger amount of a rate by `significant`
+const SIGNIFICANT_BITS: u32 = 40;
+
+/// Both amounts shifted right until the larger one fits in `SIGNIFICANT_BITS`, keeping the ratio
+fn significant((capital, shares): (u128, u128)) -> (u128, u128) {
+    let bits = 128 - capital.max(shares).leading_zeros();
+    let shift = bits.saturating_sub(SIGNIFICANT_BITS);
+    (capital >> shift, shares >> shift)
+}
@@ -2,51 +2,141 @@
 use codec::{Decode, Encode};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-use sp_runtime::{traits::SimpleArithmetic, Permill, RuntimeDebug};
-use sp_std::prelude::*;
+use sp_runtime::{
+    traits::{SaturatedConversion, SimpleArithmetic, Zero},
+    Permill, RuntimeDebug,
+};
+use sp_std::{cmp::Ordering, prelude::*};
 // use signal::ShareBank; // add functionality to `ShareBank`
 
-/// Wrapper around Permill for `Share : Capital` ratio comparisons
-/// - it is required because `from_ration_approximation(p, q)`
-/// does not support instances in which `p > q`
+mod curve;
+pub use curve::{
+    relative_shares_per_capital, BondCurve, Exponential, Linear, PiecewiseLinear, ONE,
+};
+
+/// The exact amounts of capital and shares exchanged by an action, for `Share : Capital` ratio
+/// comparisons
 /// - upon second thought, this is basically a type for negotiation over conversion
+/// - ordered by capital per share s.t. greater rates are less dilutive and equal ratios compare
+/// equal regardless of the amounts (e.g. `(5, 10) == (1, 2)`)
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Clone, Encode, Decode, RuntimeDebug)]
+pub struct ConversionRate {
+    capital: u128,
+    shares: u128,
+}
+
+impl ConversionRate {
+    /// stake_promised == shares_requested
+    pub fn parity() -> Self {
+        ConversionRate {
+            capital: 1,
+            shares: 1,
+        }
+    }
+    /// The rate of exchanging `shares` for `capital`
+    /// - exchanging nothing for nothing is `parity`
+    pub fn from_shares_and_capital<N: SimpleArithmetic + Copy>(shares: N, capital: N) -> Self {
+        if shares.is_zero() && capital.is_zero() {
+            return Self::parity();
+        }
+        ConversionRate {
+            capital: capital.saturated_into(),
+            shares: shares.saturated_into(),
+        }
+    }
+    /// Capital per share as a (capital, shares) pair
+    pub fn to_rational(&self) -> (u128, u128) {
+        (self.capital, self.shares)
+    }
+    /// Both at parity or both on the same side of it
+    pub fn same_sign(&self, other: &ConversionRate) -> bool {
+        self.shares.cmp(&self.capital) == other.shares.cmp(&other.capital)
+    }
+    /// The smaller amount over the larger one, rounded to a `Permill`
+    /// - `None` at parity
+    pub fn inner(&self) -> Option<Permill> {
+        match self.shares.cmp(&self.capital) {
+            Ordering::Greater => Some(Permill::from_rational_approximation(
+                self.capital,
+                self.shares,
+            )),
+            Ordering::Less => Some(Permill::from_rational_approximation(
+                self.shares,
+                self.capital,
+            )),
+            Ordering::Equal => None,
+        }
+    }
+    /// stake_promised < shares_requested
+    // because membership proposals that ask for more shares than capital might/should be automatically rejected (grants are a separate process)
+    pub fn is_capital_over_share(&self) -> bool {
+        self.capital < self.shares
+    }
+}
+
+/// `a * b` as a (high, low) pair of u128 words, so that products of amounts never overflow
+fn full_mul(a: u128, b: u128) -> (u128, u128) {
+    const LOW: u128 = u64::max_value() as u128;
+    let (a_high, a_low) = (a >> 64, a & LOW);
+    let (b_high, b_low) = (b >> 64, b & LOW);
+    let low_low = a_low * b_low;
+    let high_low = a_high * b_low;
+    let low_high = a_low * b_high;
+    let cross = (low_low >> 64) + (high_low & LOW) + (low_high & LOW);
+    let low = (cross << 64) | (low_low & LOW);
+    let high = a_high * b_high + (high_low >> 64) + (low_high >> 64) + (cross >> 64);
+    (high, low)
+}
+
+impl Ord for ConversionRate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        full_mul(self.capital, other.shares).cmp(&full_mul(other.capital, self.shares))
+    }
+}
+
+impl PartialOrd for ConversionRate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for ConversionRate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for ConversionRate {}
+
+/// The action that a bond is required for, wrapping the base bond for that action
+/// - applicants bond capital while sponsors and voters bond shares
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
-pub enum ConversionRate {
-    // stake_promised == shares_requested
-    Parity,
-    // stake_promised < shares_requested
-    CapitalOverShare(Permill),
-    // shares_requested < stake_promised
-    ShareOverCapital(Permill),
-    // TODO: add `MoneyOverBitches` variant for weezy protocol
+#[non_exhaustive]
+pub enum BondType<Capital, Shares> {
+    Applicant(Capital),
+    Sponsor(Shares),
+    Vote(Shares),
 }
 
-// leave out for CI because not immediately used
-// impl ConversionRate {
-//     pub(crate) fn same_sign(&self, other: &ConversionRate) -> bool {
-//         match (self, other) {
-//             (&ConversionRate::Parity, &ConversionRate::Parity) => true,
-//             (&ConversionRate::CapitalOverShare(_), &ConversionRate::CapitalOverShare(_)) => true,
-//             (&ConversionRate::ShareOverCapital(_), &ConversionRate::ShareOverCapital(_)) => true,
-//             _ => false,
-//         }
-//     }
-//     pub(crate) fn inner(&self) -> Option<Permill> {
-//         match self {
-//             &ConversionRate::CapitalOverShare(amount) => Some(amount),
-//             &ConversionRate::ShareOverCapital(amount) => Some(amount),
-//             _ => None,
-//         }
-//     }
-//     // because membership proposals that ask for more shares than capital might/should be automatically rejected (grants are a separate process)
-//     pub(crate) fn is_capital_over_share(&self) -> bool {
-//         match self {
-//             &ConversionRate::CapitalOverShare(_) => true,
-//             _ => false,
-//         }
-//     }
-// }
+impl<Capital: SimpleArithmetic, Shares: SimpleArithmetic> BondType<Capital, Shares> {
+    /// The same kind of bond multiplied by `multiplier` millionths (saturating, rounded down)
+    pub fn saturating_scale(self, multiplier: u128) -> Self {
+        let scale = |amount: u128| amount.saturating_mul(multiplier) / ONE;
+        match self {
+            BondType::Applicant(capital) => {
+                BondType::Applicant(scale(capital.saturated_into()).saturated_into())
+            }
+            BondType::Sponsor(shares) => {
+                BondType::Sponsor(scale(shares.saturated_into()).saturated_into())
+            }
+            BondType::Vote(shares) => {
+                BondType::Vote(scale(shares.saturated_into()).saturated_into())
+            }
+        }
+    }
+}
 
 /// Intended for implementation by the module? for now
 ///
@@ -61,11 +151,16 @@ pub trait ActionBond {
 // need a configuration for each of these that relates
 // to the underlying meta state requirements
 pub trait CalculateCollateralReq {
-    type Collateral: SimpleArithmetic;
+    type Shares: SimpleArithmetic;
+    type Capital: SimpleArithmetic;
+    /// Scale the base `bond` by how `new_parity` (of the proposed action) compares with
+    /// `existing_parity` (of the bank), i.e. with a `BondCurve`
+    /// - returns the same `BondType` variant as `bond`
     fn calculate_collateral_req(
+        bond: BondType<Self::Capital, Self::Shares>,
         new_parity: ConversionRate,
         existing_parity: ConversionRate,
-    ) -> Self::Collateral;
+    ) -> BondType<Self::Capital, Self::Shares>;
 }
 
 // super far out
@@ -0,0 +1,132 @@
+//! Membership criteria (see #27)
+//! - `membership_application` checks `T::AdmissionPolicy` before reserving the application bond
+//! - policies compose as tuples, e.g. `(MinimumStake<Min>, OneOpenApplication)`
+use super::*;
+
+use frame_support::traits::Contains;
+use sp_std::marker::PhantomData;
+
+/// Criteria that every membership application must meet
+pub trait AdmissionPolicy<T: Trait> {
+    /// Ok if `applicant` may apply to exchange `stake_promised` for `shares_requested`,
+    /// otherwise the error that describes the violated criterion
+    fn admit(
+        applicant: &T::AccountId,
+        stake_promised: BalanceOf<T>,
+        shares_requested: Shares,
+    ) -> Result<(), Error<T>>;
+}
+
+/// Admits every application
+impl<T: Trait> AdmissionPolicy<T> for () {
+    fn admit(_: &T::AccountId, _: BalanceOf<T>, _: Shares) -> Result<(), Error<T>> {
+        Ok(())
+    }
+}
+
+macro_rules! impl_admission_policy_for_tuples {
+    ($($policy:ident),+) => {
+        /// Admits applications that every policy in the tuple admits
+        impl<T: Trait, $($policy: AdmissionPolicy<T>),+> AdmissionPolicy<T> for ($($policy,)+) {
+            fn admit(
+                applicant: &T::AccountId,
+                stake_promised: BalanceOf<T>,
+                shares_requested: Shares,
+            ) -> Result<(), Error<T>> {
+                $(<$policy as AdmissionPolicy<T>>::admit(applicant, stake_promised, shares_requested)?;)+
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_admission_policy_for_tuples!(A);
+impl_admission_policy_for_tuples!(A, B);
+impl_admission_policy_for_tuples!(A, B, C);
+impl_admission_policy_for_tuples!(A, B, C, D);
+impl_admission_policy_for_tuples!(A, B, C, D, E);
+impl_admission_policy_for_tuples!(A, B, C, D, E, F);
+
+/// Rejects applications with more than `Max` shares per capital relative to the bank, in
+/// millionths (see `collateral::relative_shares_per_capital`)
+/// - `collateral::ONE` rejects any application more dilutive than the bank's ratio
+pub struct MaximumDilution<Max>(PhantomData<Max>);
+
+impl<T: Trait, Max: Get<u128>> AdmissionPolicy<T> for MaximumDilution<Max> {
+    fn admit(
+        _: &T::AccountId,
+        stake_promised: BalanceOf<T>,
+        shares_requested: Shares,
+    ) -> Result<(), Error<T>> {
+        let banks_rate = <Module<T>>::banks_conversion_rate()?;
+        let rate = <Module<T>>::conversion_comparison(shares_requested, stake_promised);
+        ensure!(
+            relative_shares_per_capital(&rate, &banks_rate) <= Max::get(),
+            Error::<T>::MembershipApplicationIsRidiculous
+        );
+        Ok(())
+    }
+}
+
+/// Rejects applications that promise less than `Min` stake
+pub struct MinimumStake<Min>(PhantomData<Min>);
+
+impl<T: Trait, Min: Get<BalanceOf<T>>> AdmissionPolicy<T> for MinimumStake<Min> {
+    fn admit(_: &T::AccountId, stake_promised: BalanceOf<T>, _: Shares) -> Result<(), Error<T>> {
+        ensure!(stake_promised >= Min::get(), Error::<T>::StakeBelowMinimum);
+        Ok(())
+    }
+}
+
+/// Rejects applications that request more than `Cap` of the bank's existing shares
+pub struct MaximumShareRequest<Cap>(PhantomData<Cap>);
+
+impl<T: Trait, Cap: Get<Permill>> AdmissionPolicy<T> for MaximumShareRequest<Cap> {
+    fn admit(_: &T::AccountId, _: BalanceOf<T>, shares_requested: Shares) -> Result<(), Error<T>> {
+        ensure!(
+            shares_requested <= Cap::get() * <Module<T>>::bank_account().shares,
+            Error::<T>::TooManySharesRequested
+        );
+        Ok(())
+    }
+}
+
+/// Only admits applicants in `List`
+pub struct AllowList<List>(PhantomData<List>);
+
+impl<T: Trait, List: Contains<T::AccountId>> AdmissionPolicy<T> for AllowList<List> {
+    fn admit(applicant: &T::AccountId, _: BalanceOf<T>, _: Shares) -> Result<(), Error<T>> {
+        ensure!(List::contains(applicant), Error::<T>::ApplicantNotAllowed);
+        Ok(())
+    }
+}
+
+/// Rejects applicants in `List`
+pub struct DenyList<List>(PhantomData<List>);
+
+impl<T: Trait, List: Contains<T::AccountId>> AdmissionPolicy<T> for DenyList<List> {
+    fn admit(applicant: &T::AccountId, _: BalanceOf<T>, _: Shares) -> Result<(), Error<T>> {
+        ensure!(!List::contains(applicant), Error::<T>::ApplicantDenied);
+        Ok(())
+    }
+}
+
+/// Rejects applicants whose latest application is still open, i.e. not yet executed, rejected,
+/// expired or withdrawn (see `LatestMembershipApplication`)
+/// - the way to reject duplicate applications, since proposal ids include the applicant's nonce
+/// and so never collide
+pub struct OneOpenApplication;
+
+impl<T: Trait> AdmissionPolicy<T> for OneOpenApplication {
+    fn admit(applicant: &T::AccountId, _: BalanceOf<T>, _: Shares) -> Result<(), Error<T>> {
+        let open = <LatestMembershipApplication<T>>::get(applicant)
+            .and_then(|id| <MembershipApplications<T>>::get(id))
+            .map(|application| match application.stage {
+                ProposalStage::Application | ProposalStage::Voting | ProposalStage::Passed => true,
+                _ => false,
+            })
+            .unwrap_or(false);
+        ensure!(!open, Error::<T>::ApplicantHasOpenApplication);
+        Ok(())
+    }
+}
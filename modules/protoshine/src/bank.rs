@@ -17,8 +17,12 @@ pub struct ShareProfile {
 }
 
 impl ShareProfile {
+    /// Shares that are not reserved for ongoing sponsorships/votes
+    pub(crate) fn unreserved_shares(&self) -> Shares {
+        self.total_shares.saturating_sub(self.reserved_shares)
+    }
     pub(crate) fn can_reserve(&self, amount: Shares) -> bool {
-        amount >= self.total_shares - self.reserved_shares
+        amount <= self.unreserved_shares()
     }
 }
 
@@ -36,6 +40,18 @@ pub enum Owner<AccountId> {
     Owned(AccountId),
 }
 
+// useful for getting the bank_balance when you need to calculate the bank's collateralization ratio
+// - see ../collateral
+impl<AccountId> Owner<AccountId> {
+    pub(crate) fn inner(self) -> Option<AccountId> {
+        if let Owner::Owned(account) = self {
+            Some(account)
+        } else {
+            None
+        }
+    }
+}
+
 /// Bank Object
 /// relevant when
 /// - shares are issued (for membership)
@@ -13,23 +13,54 @@ use bank::{Bank, Owner, ShareProfile, BANK_ID};
 use signal::ShareBank;
 
 mod vote;
-use vote::{Approved, MembershipVotingState, Vote, VoteThreshold};
+use vote::{
+    Approved, Conviction, Delegation, Vote, VoteError, VoteThreshold, VoteWeighting, VotingState,
+};
+
+pub mod admission;
+use admission::AdmissionPolicy;
+
+mod proposal;
+use proposal::{Proposal, ProposalKind};
+
+mod migration;
+use signal::versioning::Version;
 
 use codec::{Decode, Encode};
+use collateral::{
+    relative_shares_per_capital, ActionBond, BondCurve, BondType, CalculateCollateralReq,
+    ConversionRate,
+};
 use frame_support::traits::{Currency, ExistenceRequirement, Get, ReservableCurrency};
 use frame_support::{decl_error, decl_event, decl_module, decl_storage, ensure};
 use frame_system::{self as system, ensure_signed};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-use sp_runtime::traits::AccountIdConversion; // Saturating
+use sp_runtime::traits::{AccountIdConversion, Hash, One, Saturating, Zero};
 use sp_runtime::{DispatchResult, Permill, RuntimeDebug};
 use sp_std::prelude::*;
 
-// TODO: replace with hashes as per recent issue
-type ProposalIndex = u32;
 pub type Shares = u32;
 pub type BalanceOf<T> =
     <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+pub type MembershipProposalOf<T> = MembershipProposal<
+    <T as frame_system::Trait>::AccountId,
+    BalanceOf<T>,
+    <T as frame_system::Trait>::BlockNumber,
+>;
+pub type GrantProposalOf<T> = GrantProposal<
+    <T as frame_system::Trait>::AccountId,
+    BalanceOf<T>,
+    <T as frame_system::Trait>::BlockNumber,
+>;
+pub type MetaProposalOf<T> = MetaProposal<
+    <T as frame_system::Trait>::AccountId,
+    BalanceOf<T>,
+    <T as frame_system::Trait>::BlockNumber,
+>;
+pub type ParameterChangeOf<T> =
+    ParameterChange<BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
+pub type CrowdfundOf<T> = Crowdfund<BalanceOf<T>>;
 
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
@@ -44,14 +75,16 @@ pub enum ProposalStage {
     Passed,
     /// Already executed
     Law,
+    /// Failed to pass, either because passage became impossible or because the
+    /// `VotingPeriod` ended without meeting the threshold
+    Rejected,
 }
 
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
 /// Proposal for membership changes to the LLC
+/// - keyed by the hash of its terms and a nonce (see `Module::membership_proposal_id`)
 pub struct MembershipProposal<AccountId, BalanceOf, BlockNumber> {
-    /// Unique proposal index
-    index: ProposalIndex,
     /// The applicant
     who: AccountId,
     /// The collateral promised and slowly staked over the duration of the proposal process
@@ -60,10 +93,126 @@ pub struct MembershipProposal<AccountId, BalanceOf, BlockNumber> {
     stake_promised: BalanceOf,
     /// The reward that the bidder has requested for successfully joining the society.
     shares_requested: Shares,
+    /// The application bond actually reserved from the applicant
+    /// - refunds and slashes use this amount so they stay correct if `MembershipProposalBond`
+    /// changes while the application is open
+    bond: BalanceOf,
     /// Current stage of the proposal
     stage: ProposalStage,
-    /// if `ApplicationTimeLimit` is exceeded past this time_proposed, the application is removed
-    /// - TODO: use #7 to get rid of this or wrap it in `ProposalStage::Application` if possible
+    /// if `ApplicationTimeLimit` is exceeded past this time_proposed without sponsorship,
+    /// the application is removed (see `ProposalExpiries`)
+    time_proposed: BlockNumber,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+/// Terms and progress of a crowdfunded membership application (see `Crowdfunds`)
+/// - backers contribute the application's `bond` and `stake_promised` (see `Contributions`)
+pub struct Crowdfund<BalanceOf> {
+    /// Portion of `shares_requested` split between the backers in proportion to their
+    /// contributions, set by the applicant when the application is made
+    backer_share: Permill,
+    /// Reserved by the applicant when the application is made and refunded or slashed like an
+    /// application bond, so that applicants have something at stake too
+    deposit: BalanceOf,
+    /// Bond contributed so far
+    bond_raised: BalanceOf,
+    /// Stake contributed so far
+    stake_raised: BalanceOf,
+}
+
+impl<BalanceOf: PartialOrd> Crowdfund<BalanceOf> {
+    /// Whether the full `bond` and `stake_promised` of the application were raised
+    pub(crate) fn is_funded(&self, bond: &BalanceOf, stake_promised: &BalanceOf) -> bool {
+        &self.bond_raised >= bond && &self.stake_raised >= stake_promised
+    }
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+/// How a passed grant is paid out of the bank (see `Recipients`)
+pub enum GrantSchedule<BlockNumber> {
+    /// Paid in full at the end of the block in which the grant passed
+    Immediate,
+    /// Paid in `payments` equal installments, the first at the end of the block in which the
+    /// grant passed and the rest every `period` blocks after it
+    Installments { payments: u32, period: BlockNumber },
+}
+
+impl<BlockNumber> GrantSchedule<BlockNumber> {
+    /// Number of payments the grant is split into
+    pub(crate) fn payments(&self) -> u32 {
+        match self {
+            GrantSchedule::Immediate => 1,
+            GrantSchedule::Installments { payments, .. } => *payments,
+        }
+    }
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+/// Proposal to pay a grant out of the bank
+/// - keyed by the hash of its terms and a nonce (see `Module::grant_proposal_id`)
+/// - grants spend capital without burning shares so they lower the capital backing each share
+pub struct GrantProposal<AccountId, BalanceOf, BlockNumber> {
+    /// The applicant
+    who: AccountId,
+    /// The account that receives the grant
+    recipient: AccountId,
+    /// The total amount paid out of the bank
+    amount: BalanceOf,
+    /// How the amount is paid once the proposal passes
+    schedule: GrantSchedule<BlockNumber>,
+    /// Number of payments made so far
+    payments_made: u32,
+    /// The application bond actually reserved from the applicant
+    bond: BalanceOf,
+    /// Current stage of the proposal
+    /// - stays `ProposalStage::Passed` until the last payment is made
+    stage: ProposalStage,
+    /// if `ApplicationTimeLimit` is exceeded past this time_proposed without sponsorship,
+    /// the application is removed (see `ProposalExpiries`)
+    time_proposed: BlockNumber,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[non_exhaustive]
+/// A new value for one of the economic parameters kept in storage
+/// - every change is checked when it is proposed and again when it passes, since shares may have
+/// changed hands in between (see `Module::ensure_valid_parameter_change`)
+pub enum ParameterChange<BalanceOf, BlockNumber> {
+    /// Must not be zero
+    MembershipProposalBond(BalanceOf),
+    /// Must not be zero nor exceed the shares of the largest member
+    MembershipSponsorBond(Shares),
+    /// Must not be zero nor exceed the shares of the largest member
+    MembershipVoteBond(Shares),
+    /// Must not be zero because no application could be executed
+    MaximumShareIssuance(Permill),
+    /// Must not be zero nor exceed `MetaConsensusThreshold`
+    MembershipConsensusThreshold(Permill),
+    /// Must not be zero because batches run every `BatchPeriod` blocks
+    BatchPeriod(BlockNumber),
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+/// Proposal by a member to change an economic parameter
+/// - keyed by the hash of its terms and a nonce (see `Module::meta_proposal_id`)
+/// - passes with `MetaConsensusThreshold` of a turnout of at least `MetaQuorum`
+/// (see `VoteThreshold::QuorumConsensus`)
+pub struct MetaProposal<AccountId, BalanceOf, BlockNumber> {
+    /// The member that made the proposal
+    who: AccountId,
+    /// The change applied once the proposal passes
+    change: ParameterChange<BalanceOf, BlockNumber>,
+    /// The proposal bond reserved from the proposer
+    bond: BalanceOf,
+    /// Current stage of the proposal
+    stage: ProposalStage,
+    /// if `ApplicationTimeLimit` is exceeded past this time_proposed without sponsorship,
+    /// the proposal is removed (see `ProposalExpiries`)
     time_proposed: BlockNumber,
 }
 
@@ -76,39 +225,211 @@ pub trait Trait: frame_system::Trait {
 
     /// Minimum amount of funds that should be placed in a deposit for
     /// making a membership proposal
+    /// - genesis value of the `MembershipProposalBond` storage value (see `ParameterChange`)
     type MembershipProposalBond: Get<BalanceOf<Self>>;
 
-    /// Minimum amount of shares that should be locked for sponsoring a membership proposal
+    /// Membership criteria that applications must meet (see `admission`)
+    /// - `()` admits every application that promises more than the existential deposit
+    type AdmissionPolicy: AdmissionPolicy<Self>;
+
+    /// Amount of shares that should be locked for sponsoring a membership proposal
+    /// - flat, unlike the application bond it isn't scaled by `BondCurve`
+    /// - genesis value of the `MembershipSponsorBond` storage value
     type MembershipSponsorBond: Get<Shares>;
 
     /// Uniform voting bond
-    /// TODO: reuse dynamic collateral logic from the two bonds above
-    /// when further along (see `calculate_bonds`)
+    /// - flat, unlike the application bond it isn't scaled by `BondCurve`
+    /// - genesis value of the `MembershipVoteBond` storage value
     type MembershipVoteBond: Get<Shares>;
 
     //// Maximum percentage of existing shares that can be issued in a BatchPeriod
+    /// - genesis value of the `MaximumShareIssuance` storage value
     type MaximumShareIssuance: Get<Permill>;
 
     /// Minimum threshold to pass membership proposals
-    /// - TODO: should depend on turnout and use `signal::Threshold` to foster
-    ///  modular runtime configuration
+    /// - portion of the `MembershipQuorum` in favor (see `VoteThreshold::TurnoutBiased`)
+    /// - genesis value of the `MembershipConsensusThreshold` storage value
     type MembershipConsensusThreshold: Get<Permill>;
 
+    /// Portion of all shares that must vote on a membership proposal for it to pass
+    /// - must not be zero (checked at genesis) because the sponsor's vote alone would then pass
+    /// any proposal that nobody votes against
+    type MembershipQuorum: Get<Permill>;
+
     /// Batched membership changes
+    /// - genesis value of the `BatchPeriod` storage value
     type BatchPeriod: Get<Self::BlockNumber>;
+
+    /// Maximum number of voters whose reserved shares are released from closed votes per block
+    type MaximumVoteReleases: Get<u32>;
+
+    /// Scales the application bond with the distance between an application's shares per
+    /// capital and the bank's (see `calculate_collateral_req`)
+    /// - only the application bond is dynamic; sponsor and vote bonds are flat parameters
+    type BondCurve: BondCurve;
+
+    /// Portion of the application bond slashed to the bank when an application fails
+    /// (the remainder is returned to the applicant)
+    type ApplicationBondSlash: Get<Permill>;
+
+    /// Number of blocks an application can wait for sponsorship before it is removed
+    type ApplicationTimeLimit: Get<Self::BlockNumber>;
+
+    /// Number of blocks a sponsored proposal is open to voting before the vote is decided
+    type VotingPeriod: Get<Self::BlockNumber>;
+
+    /// Number of blocks per `Conviction` lock period that reserved shares stay locked
+    /// after the proposal closes
+    type ConvictionLockPeriod: Get<Self::BlockNumber>;
+
+    /// Minimum amount of funds that should be placed in a deposit for making a grant proposal
+    type GrantProposalBond: Get<BalanceOf<Self>>;
+
+    /// Minimum amount of shares that should be locked for sponsoring a grant proposal
+    type GrantSponsorBond: Get<Shares>;
+
+    /// Minimum threshold to pass grant proposals
+    /// - portion of the `GrantQuorum` in favor (see `VoteThreshold::TurnoutBiased`)
+    /// - votes on grants share the `MembershipVoteBond`
+    type GrantConsensusThreshold: Get<Permill>;
+
+    /// Portion of all shares that must vote on a grant proposal for it to pass
+    /// - must not be zero (checked at genesis) for the same reason as `MembershipQuorum`
+    type GrantQuorum: Get<Permill>;
+
+    /// Minimum amount of funds that should be placed in a deposit for making a meta proposal
+    type MetaProposalBond: Get<BalanceOf<Self>>;
+
+    /// Minimum threshold to pass meta proposals, a portion of the turnout in favor
+    /// (see `VoteThreshold::QuorumConsensus`)
+    /// - must be more than half and at least `MembershipConsensusThreshold` (checked at genesis
+    /// and for every `ParameterChange`) so that meta proposals are at least as hard to pass as
+    /// membership proposals at every turnout
+    /// - is itself fixed so that meta proposals can't lower the bar for future meta proposals
+    type MetaConsensusThreshold: Get<Permill>;
+
+    /// Portion of all shares that must vote on a meta proposal for it to pass
+    /// - must be at least `MembershipQuorum` (checked at genesis)
+    type MetaQuorum: Get<Permill>;
 }
 
 decl_event!(
     pub enum Event<T>
     where
+        <T as frame_system::Trait>::AccountId,
         Balance = BalanceOf<T>,
         <T as frame_system::Trait>::BlockNumber,
+        <T as frame_system::Trait>::Hash,
     {
-        MembershipApplicationProposed(ProposalIndex, Balance, Shares, BlockNumber),
-    /// An application was sponsored by a member on-chain with some of 
-    /// their `Shares` at least equal to the `sponsor_quota` (metaparameter).
-	/// (index of proposal, sponsor quota for sponsorship, stake promised, shares requested)
-        MembershipApplicationSponsored(ProposalIndex, Shares, Balance, Shares),
+        /// A new application was made (proposal id, stake promised, shares requested, time proposed)
+        MembershipApplicationProposed(Hash, Balance, Shares, BlockNumber),
+        /// An application was sponsored by a member on-chain with some of
+        /// their `Shares` at least equal to the `sponsor_quota` (metaparameter).
+        /// (proposal id, sponsor quota for sponsorship, stake promised, shares requested)
+        MembershipApplicationSponsored(Hash, Shares, Balance, Shares),
+        /// An application was not sponsored within `ApplicationTimeLimit` and was removed
+        MembershipApplicationExpired(Hash),
+        /// The applicant withdrew an application before it was sponsored (proposal id, applicant)
+        MembershipApplicationWithdrawn(Hash, AccountId),
+        /// A membership proposal was rejected by vote or ran out of `VotingPeriod`
+        /// (proposal id, shares in favor, shares against)
+        MembershipApplicationRejected(Hash, Shares, Shares),
+        /// A membership proposal passed and is scheduled for execution in the next batch
+        /// (proposal id, shares in favor, shares against)
+        MembershipApplicationPassed(Hash, Shares, Shares),
+        /// A passed membership proposal was executed and the applicant joined the organization
+        /// (proposal id, new member, stake transferred to the bank, shares issued)
+        MembershipApplicationExecuted(Hash, AccountId, Balance, Shares),
+        /// A passed membership proposal could not be executed because the applicant could
+        /// not transfer the stake promised so it is rejected (proposal id, applicant)
+        MembershipApplicationExecutionFailed(Hash, AccountId),
+        /// A passed membership proposal did not fit under the `MaximumShareIssuance` for this
+        /// batch, or is queued after one that didn't, so it waits for the next batch
+        /// (proposal id, shares requested)
+        MembershipApplicationDeferred(Hash, Shares),
+        /// A member burned shares in exchange for capital from the bank
+        /// (member, shares burned, capital paid out)
+        MembershipSharesBurned(AccountId, Shares, Balance),
+        /// A member burned all of their shares and left the organization
+        MemberLeft(AccountId),
+        /// All shares reserved for sponsoring and voting on a closed proposal were released
+        VoteSharesReleased(Hash),
+        /// A member lent the voting weight of their shares to another member
+        /// (delegator, delegate, shares)
+        SharesDelegated(AccountId, AccountId, Shares),
+        /// A member took back the voting weight lent to another member (delegator, delegate)
+        SharesUndelegated(AccountId, AccountId),
+        /// A member voted on an open proposal
+        /// (proposal id, voter, the voter's whole vote after this one, its conviction)
+        Voted(Hash, AccountId, Vote, Conviction),
+        /// A member retracted their vote on an open proposal (proposal id, voter)
+        VoteRetracted(Hash, AccountId),
+        /// The shares reserved for a vote with conviction were released after their lock
+        /// (proposal id, voter, shares)
+        ConvictionVoteUnlocked(Hash, AccountId, Shares),
+        /// The application bond was returned to the applicant, or to a backer of a crowdfunded
+        /// application (proposal id, applicant or backer, bond)
+        MembershipApplicationBondRefunded(Hash, AccountId, Balance),
+        /// Part of the application bond was slashed to the bank and the rest returned
+        /// (proposal id, applicant or backer, amount slashed, amount returned)
+        MembershipApplicationBondSlashed(Hash, AccountId, Balance, Balance),
+        /// An application is raising its bond and stake from backers, who receive part of the
+        /// shares requested (proposal id, backer share)
+        MembershipApplicationCrowdfunded(Hash, Permill),
+        /// A backer contributed to a crowdfunded application (proposal id, backer, bond, stake)
+        MembershipApplicationBacked(Hash, AccountId, Balance, Balance),
+        /// A crowdfunded application raised its full bond and stake and can be sponsored
+        MembershipApplicationFunded(Hash),
+        /// The stake contributed to a failed crowdfunded application was returned
+        /// (proposal id, backer, stake)
+        MembershipApplicationStakeRefunded(Hash, AccountId, Balance),
+        /// A backer received their part of the shares of an executed crowdfunded application
+        /// (proposal id, backer, shares)
+        MembershipSharesToBacker(Hash, AccountId, Shares),
+        /// A new grant application was made (proposal id, recipient, amount, time proposed)
+        GrantApplicationProposed(Hash, AccountId, Balance, BlockNumber),
+        /// A grant application was sponsored by a member (proposal id, sponsor bond, amount)
+        GrantApplicationSponsored(Hash, Shares, Balance),
+        /// A grant application was not sponsored within `ApplicationTimeLimit` and was removed
+        GrantApplicationExpired(Hash),
+        /// A grant proposal passed and its payments are scheduled
+        /// (proposal id, shares in favor, shares against)
+        GrantApplicationPassed(Hash, Shares, Shares),
+        /// A grant proposal was rejected by vote or ran out of `VotingPeriod`
+        /// (proposal id, shares in favor, shares against)
+        GrantApplicationRejected(Hash, Shares, Shares),
+        /// A grant payment was transferred out of the bank, leaving the bank's collateralization
+        /// ratio at its shares over its capital
+        /// (proposal id, recipient, amount, bank shares, bank capital)
+        GrantPaid(Hash, AccountId, Balance, Shares, Balance),
+        /// The bank couldn't cover a grant payment so it is retried after `BatchPeriod`
+        /// (proposal id, recipient, amount)
+        GrantPaymentDeferred(Hash, AccountId, Balance),
+        /// The grant application bond was returned to the applicant (proposal id, applicant, bond)
+        GrantApplicationBondRefunded(Hash, AccountId, Balance),
+        /// Part of the grant application bond was slashed to the bank and the rest returned
+        /// (proposal id, applicant, amount slashed, amount returned)
+        GrantApplicationBondSlashed(Hash, AccountId, Balance, Balance),
+        /// A member proposed a parameter change (proposal id, proposer, time proposed)
+        MetaProposalProposed(Hash, AccountId, BlockNumber),
+        /// A meta proposal was sponsored by a member (proposal id, sponsor bond)
+        MetaProposalSponsored(Hash, Shares),
+        /// A meta proposal was not sponsored within `ApplicationTimeLimit` and was removed
+        MetaProposalExpired(Hash),
+        /// A meta proposal was rejected by vote or ran out of `VotingPeriod`
+        /// (proposal id, shares in favor, shares against)
+        MetaProposalRejected(Hash, Shares, Shares),
+        /// A meta proposal passed but its change was no longer valid so it was rejected without
+        /// taking effect (proposal id, shares in favor, shares against)
+        MetaProposalInvalidated(Hash, Shares, Shares),
+        /// The meta proposal bond was returned to the proposer (proposal id, proposer, bond)
+        MetaProposalBondRefunded(Hash, AccountId, Balance),
+        /// Part of the meta proposal bond was slashed to the bank and the rest returned
+        /// (proposal id, proposer, amount slashed, amount returned)
+        MetaProposalBondSlashed(Hash, AccountId, Balance, Balance),
+        /// A meta proposal passed and its parameter change took effect
+        /// (proposal id, block the change took effect, shares in favor, shares against)
+        ParameterChanged(Hash, BlockNumber, Shares, Shares),
     }
 );
 
@@ -123,21 +444,22 @@ decl_error! {
         InvalidMembershipApplication,
         /// Applicant can't cover collateral requirement for membership application
         InsufficientMembershipApplicantCollateral,
-        /// Index doesn't haven associated membership proposal
+        /// Id doesn't have an associated membership proposal
         IndexWithNoAssociatedMembershipProposal,
+        /// Id doesn't have an associated proposal of any kind
+        IndexWithNoAssociatedProposal,
         /// Required sponsorship bond exceeds upper bound inputted by user
         SponsorBondExceedsExpectations,
         /// Sponsor doesn't have enough shares to sponsor membership app
         InsufficientMembershipSponsorCollateral,
-        /// Sponsor doesn't have enough shares to vote on membership app
+        /// Member doesn't have enough unreserved shares (held at the vote's snapshot) to vote on
+        /// membership app
         InsufficientMembershipVoteCollateral,
         /// Every vote inputs a magnitude and this must be above the minimum vote bond
         /// (expressed in shares)
         VoteMagnitudeBelowMinimumVoteBond,
         /// The vote state was never sponsored correctly so its vote state was not initialized
         VoteStateUninitialized,
-        /// New voting option is not handled by the big match statement
-        NewVotingOptionNotHandled,
         /// Could split this into at least `SponsorRequestForNonApplication`
         /// and `VoteOnNonVotingProposal`
         RequestInWrongStage,
@@ -148,28 +470,150 @@ decl_error! {
         /// Paths that are unlikely
         /// - delete all of these and resolve paths before use
         UnlikelyPathToBeDealtWith,
+        /// Burning zero shares is not a valid exit
+        InvalidShareBurn,
+        /// Member doesn't have enough unreserved shares to burn the requested amount;
+        /// shares reserved for open votes and sponsorships are released once those resolve
+        InsufficientUnreservedShares,
+        /// Only the applicant can withdraw their application
+        NotTheApplicant,
+        /// Sponsored applications can't be withdrawn because members already reserved shares
+        /// to vote on them
+        CannotWithdrawSponsoredApplication,
+        /// Delegating zero shares is not a valid delegation
+        InvalidDelegation,
+        /// Members can't delegate to themselves
+        CannotDelegateToSelf,
+        /// Shares can only be delegated to members
+        DelegateNotAMember,
+        /// Members can only delegate to one member at a time; undelegate first
+        AlreadyDelegated,
+        /// The member hasn't delegated any shares
+        NoDelegation,
+        /// The delegate's votes on open proposals still reserve the delegated shares
+        DelegatedSharesInUse,
+        /// Changing a vote can't lower its conviction because the lock would be escaped
+        ConvictionCannotBeLowered,
+        /// Abstaining doesn't carry weight so it can't carry conviction either
+        ConvictionOnAbstain,
+        /// The vote would overflow the tally
+        VoteTallyOverflow,
+        /// The member's vote isn't part of the tally it is removed from
+        VoteNotInTally,
+        /// The member has no vote on the proposal
+        NoVoteToRetract,
+        /// The sponsor's vote backs the sponsorship until the proposal closes
+        CannotRetractSponsorVote,
+        /// The sponsor can only add to their vote in favor, which backs the sponsorship
+        CannotReplaceSponsorVote,
+        /// Poorly formed grant application because the amount is zero or the schedule has no
+        /// payments or no period between them
+        InvalidGrantApplication,
+        /// Applicant can't cover collateral requirement for grant application
+        InsufficientGrantApplicantCollateral,
+        /// Proposer can't cover collateral requirement for meta proposal
+        InsufficientMetaProposerCollateral,
+        /// The parameter change would leave the module unusable (i.e. a zero `BatchPeriod`) or
+        /// make membership proposals harder to pass than meta proposals
+        InvalidParameterChange,
         /// Enforcement of membership criteria standards
-        /// i.e. requesting more shares than capital committed
+        /// i.e. requesting more shares per capital relative to the bank than the
+        /// `AdmissionPolicy` allows
         MembershipApplicationIsRidiculous,
+        /// The stake promised is below the `AdmissionPolicy` minimum
+        StakeBelowMinimum,
+        /// The shares requested are more than the `AdmissionPolicy` allows of existing shares
+        TooManySharesRequested,
+        /// The applicant isn't on the `AdmissionPolicy` allow list
+        ApplicantNotAllowed,
+        /// The applicant is on the `AdmissionPolicy` deny list
+        ApplicantDenied,
+        /// The applicant's previous application is still open
+        ApplicantHasOpenApplication,
+        /// Only crowdfunded applications take contributions from backers
+        NotACrowdfundedApplication,
+        /// The contribution has neither bond nor stake
+        EmptyContribution,
+        /// The contribution would raise more than the application's bond or stake promised
+        ContributionExceedsRemaining,
+        /// Backer can't cover their contribution to a crowdfunded application
+        InsufficientBackerCollateral,
+        /// Crowdfunded applications can only be sponsored once their bond and stake are raised
+        ApplicationNotFullyFunded,
+        /// Storage is still being migrated to the current layout (see `on_initialize`)
+        StorageMigrationInProgress,
+    }
+}
+
+impl<T: Trait> From<VoteError> for Error<T> {
+    fn from(error: VoteError) -> Self {
+        match error {
+            VoteError::Overflow => Error::<T>::VoteTallyOverflow,
+            VoteError::NotInTally => Error::<T>::VoteNotInTally,
+            VoteError::ConvictionLowered => Error::<T>::ConvictionCannotBeLowered,
+            VoteError::ConvictionOnAbstain => Error::<T>::ConvictionOnAbstain,
+        }
     }
 }
 
 decl_storage! {
     trait Store for Module<T: Trait> as Protoshine {
-        // // DEPRECATED UNTIL #7 is implemented and then this will be useful for
-        // // iterating over all proposals to purge old ones
-        // MembershipApplicationQ get(fn membership_application_q): Vec<MembershipProposal<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+        /// Version of the storage layout, used to run migrations once in `on_initialize`
+        StorageVersion get(fn storage_version)
+            build(|_: &GenesisConfig<T>| migration::CURRENT_VERSION): Version;
+        /// Progress of a `Version::V1` chain's migration to hash proposal ids, which takes a
+        /// chunk of legacy proposals in each block
+        HashedIdMigration: Option<migration::HashedIdProgress<T::Hash>>;
+
+        /// Minimum application bond for membership proposals
+        pub MembershipProposalBond get(fn membership_proposal_bond)
+            build(|_: &GenesisConfig<T>| T::MembershipProposalBond::get()): BalanceOf<T>;
+        /// Shares reserved by sponsors of membership and meta proposals
+        pub MembershipSponsorBond get(fn membership_sponsor_bond)
+            build(|_: &GenesisConfig<T>| T::MembershipSponsorBond::get()): Shares;
+        /// Minimum vote on any proposal
+        pub MembershipVoteBond get(fn membership_vote_bond)
+            build(|_: &GenesisConfig<T>| T::MembershipVoteBond::get()): Shares;
+        /// Maximum portion of existing shares issued in a batch
+        pub MaximumShareIssuance get(fn maximum_share_issuance)
+            build(|_: &GenesisConfig<T>| T::MaximumShareIssuance::get()): Permill;
+        /// Threshold recorded by membership votes when they are opened
+        pub MembershipConsensusThreshold get(fn membership_consensus_threshold)
+            build(|_: &GenesisConfig<T>| T::MembershipConsensusThreshold::get()): Permill;
+        /// Blocks between batches of membership changes
+        pub BatchPeriod get(fn batch_period)
+            build(|_: &GenesisConfig<T>| T::BatchPeriod::get()): T::BlockNumber;
+        /// Every parameter change made by a meta proposal with the block it took effect, in order
+        pub ParameterHistory get(fn parameter_history): Vec<(T::BlockNumber, ParameterChangeOf<T>)>;
+
+        /// Applications of any kind that expire at each block unless they were sponsored by then
+        pub ProposalExpiries get(fn proposal_expiries):
+            map T::BlockNumber => Vec<T::Hash>;
+        /// Sponsored proposals that expire at each block unless they passed by then
+        pub VoteExpiries get(fn vote_expiries):
+            map T::BlockNumber => Vec<T::Hash>;
 
         /// Applications for membership into the organization
         pub MembershipApplications get(fn membership_applications):
-            map ProposalIndex => Option<MembershipProposal<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+            map T::Hash => Option<MembershipProposal<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
         /// Number of proposals that have been made.
-        pub MembershipApplicationCount get(fn membership_application_count): ProposalIndex;
-        /// Membership proposal voting state
-        pub MembershipVoteStates get(fn membership_vote_states):
-            map ProposalIndex => Option<MembershipVotingState>;
+        pub MembershipApplicationCount get(fn membership_application_count): u32;
+        /// Number of applications made by each account, used as the nonce in proposal ids
+        pub MembershipApplicationNonces get(fn membership_application_nonce): map T::AccountId => u32;
+        /// Latest application made by each account (see `admission::OneOpenApplication`)
+        pub LatestMembershipApplication get(fn latest_membership_application):
+            map T::AccountId => Option<T::Hash>;
+        /// Terms and progress of crowdfunded applications
+        pub Crowdfunds get(fn crowdfunds): map T::Hash => Option<CrowdfundOf<T>>;
+        /// (backer, bond, stake) reserved by each backer of a crowdfunded application until it
+        /// is executed or fails
+        pub Contributions get(fn contributions):
+            map T::Hash => Vec<(T::AccountId, BalanceOf<T>, BalanceOf<T>)>;
+        /// Voting state of each sponsored proposal, whatever its kind
+        pub VoteStates get(fn vote_states):
+            map T::Hash => Option<VotingState>;
         /// Membership proposal indices that have been approved but not yet absorbed.
-        pub MembershipApprovals get(fn membership_approvals): Vec<ProposalIndex>;
+        pub MembershipApprovals get(fn membership_approvals): Vec<T::Hash>;
 
         /// Members should be replaced by group scaling logic
         Members get(fn members) build(|config: &GenesisConfig<T>| {
@@ -201,15 +645,77 @@ decl_storage! {
             }).collect::<Vec<_>>()
             // will have to type alias (Shares, Shares) to some struct instead of whatever this is
         }): map T::AccountId => Option<ShareProfile>;
-        /// Double Map from ProposalIndex => AccountId => Maybe(Vote)
+        /// History of each member's `total_shares` as (block, total_shares) in block order
+        /// - at most one checkpoint per block so the shares at any block are found by binary search
+        pub ShareCheckpoints get(fn share_checkpoints) build(|config: &GenesisConfig<T>| {
+            config.member_buy_in.iter().map(|(who, _, shares_requested)| {
+                (who.clone(), vec![(T::BlockNumber::zero(), *shares_requested)])
+            }).collect::<Vec<_>>()
+        }): map T::AccountId => Vec<(T::BlockNumber, Shares)>;
+        /// Block before the one in which each open vote was sponsored
+        /// - each member's vote is capped at their `total_shares` at the end of this block
+        pub VoteSnapshots get(fn vote_snapshot): map T::Hash => Option<T::BlockNumber>;
+        /// Double Map from proposal id => AccountId => Maybe(Vote)
         VotesByMembers get(fn votes_by_members):
-            double_map ProposalIndex, hasher(twox_64_concat) T::AccountId => Option<Vote>;
-        // TODO: add recipients vector for scheduled payments in `vote`
+            double_map T::Hash, hasher(twox_64_concat) T::AccountId => Option<Vote>;
+        /// Members with an entry in `VotesByMembers` for each proposal (the sponsor included)
+        /// - used to release reserved shares once the proposal closes
+        pub Voters get(fn voters): map T::Hash => Vec<T::AccountId>;
+        /// Proposals in `ProposalStage::Voting`
+        /// - their `all_voters` is kept equal to the bank's shares as shares are issued or burned
+        pub OpenVotes get(fn open_votes): Vec<T::Hash>;
+        /// The delegation made by each member, if any (delegator => delegation)
+        pub Delegations get(fn delegation): map T::AccountId => Option<Delegation<T::AccountId>>;
+        /// Members that delegated to each member (delegate => delegators) in the order they delegated
+        pub DelegatorsOf get(fn delegators_of): map T::AccountId => Vec<T::AccountId>;
+        /// Shares reserved from delegators for a member's vote on a proposal
+        /// (proposal id => voter => [(delegator, shares)])
+        pub DelegatedVoteDraws get(fn delegated_vote_draws):
+            double_map T::Hash, hasher(twox_64_concat) T::AccountId => Vec<(T::AccountId, Shares)>;
+        /// Conviction of each member's vote on a proposal (absent for `Conviction::None`)
+        VoteConvictions get(fn vote_conviction):
+            double_map T::Hash, hasher(twox_64_concat) T::AccountId => Conviction;
+        /// Votes with conviction whose reserved shares are released at a block
+        /// (block => [(proposal id, voter, shares)])
+        pub ConvictionUnlocks get(fn conviction_unlocks):
+            map T::BlockNumber => Vec<(T::Hash, T::AccountId, Shares)>;
+        /// Closed proposals with reserved shares that have yet to be released
+        /// - drained in chunks of `MaximumVoteReleases` voters per block
+        /// - proposals that are `ProposalStage::Law` or `ProposalStage::Rejected` are pruned with
+        /// their vote once they leave the queue
+        pub VoteReleases get(fn vote_releases): Vec<T::Hash>;
+
+        /// Applications for grants paid out of the bank
+        pub GrantApplications get(fn grant_applications): map T::Hash => Option<GrantProposalOf<T>>;
+        /// Number of grant applications made by each account, used as the nonce in proposal ids
+        pub GrantApplicationNonces get(fn grant_application_nonce): map T::AccountId => u32;
+        /// Grant payments due at the end of each block (block => [(proposal id, recipient, amount)])
+        pub Recipients get(fn recipients): map T::BlockNumber => Vec<(T::Hash, T::AccountId, BalanceOf<T>)>;
+
+        /// Proposals to change economic parameters
+        pub MetaProposals get(fn meta_proposals): map T::Hash => Option<MetaProposalOf<T>>;
+        /// Number of meta proposals made by each member, used as the nonce in proposal ids
+        pub MetaProposalNonces get(fn meta_proposal_nonce): map T::AccountId => u32;
     }
     add_extra_genesis {
         config(member_buy_in): Vec<(T::AccountId, BalanceOf<T>, Shares)>;
 
         build(|config: &GenesisConfig<T>| {
+            assert!(
+                !T::MembershipQuorum::get().is_zero(),
+                "MembershipQuorum must not be zero because votes are decided by their turnout"
+            );
+            assert!(
+                !T::GrantQuorum::get().is_zero(),
+                "GrantQuorum must not be zero because votes are decided by their turnout"
+            );
+            assert!(
+                T::MetaConsensusThreshold::get() > Permill::from_percent(50)
+                    && T::MetaConsensusThreshold::get() >= T::MembershipConsensusThreshold::get()
+                    && T::MetaQuorum::get() >= T::MembershipQuorum::get(),
+                "meta proposals must be at least as hard to pass as membership proposals"
+            );
+
             // This is the minimum amount in the Bank Account
             let _ = T::Currency::make_free_balance_be(
                 &<Module<T>>::account_id(),
@@ -232,24 +738,102 @@ decl_module! {
         type Error = Error<T>;
         fn deposit_event() = default;
 
-        /// Minimum proposal bond
+        /// Minimum proposal bond at genesis (see the `MembershipProposalBond` storage value)
         const MembershipProposalBond: BalanceOf<T> = T::MembershipProposalBond::get();
 
-        /// Minimum sponsor bond
+        /// Sponsor bond at genesis
         const MembershipSponsorBond: Shares = T::MembershipSponsorBond::get();
 
-        /// Uniform voting bond
+        /// Uniform voting bond at genesis
         const MembershipVoteBond: Shares = T::MembershipVoteBond::get();
 
-        /// Maximum number of shares issued in a batch period
+        /// Maximum number of shares issued in a batch period at genesis
         const MaximumShareIssuance: Permill = T::MaximumShareIssuance::get();
 
-        /// Threshold requirement for membership consensus decisions (uniform for now)
+        /// Threshold requirement for membership consensus decisions at genesis
         const MembershipConsensusThreshold: Permill = T::MembershipConsensusThreshold::get();
 
-        /// Period between successive batched membership changes
+        /// Portion of all shares that must vote on membership proposals
+        const MembershipQuorum: Permill = T::MembershipQuorum::get();
+
+        /// Period between successive batched membership changes at genesis
         const BatchPeriod: T::BlockNumber = T::BatchPeriod::get();
 
+        /// Maximum number of voters whose reserved shares are released per block
+        const MaximumVoteReleases: u32 = T::MaximumVoteReleases::get();
+
+        /// Portion of the application bond slashed when an application fails
+        const ApplicationBondSlash: Permill = T::ApplicationBondSlash::get();
+
+        /// Number of blocks an application can wait for sponsorship
+        const ApplicationTimeLimit: T::BlockNumber = T::ApplicationTimeLimit::get();
+
+        /// Number of blocks a sponsored proposal is open to voting
+        const VotingPeriod: T::BlockNumber = T::VotingPeriod::get();
+
+        /// Number of blocks in each lock period of a vote with conviction
+        const ConvictionLockPeriod: T::BlockNumber = T::ConvictionLockPeriod::get();
+
+        /// Minimum grant proposal bond
+        const GrantProposalBond: BalanceOf<T> = T::GrantProposalBond::get();
+
+        /// Minimum grant sponsor bond
+        const GrantSponsorBond: Shares = T::GrantSponsorBond::get();
+
+        /// Threshold requirement for grant decisions
+        const GrantConsensusThreshold: Permill = T::GrantConsensusThreshold::get();
+
+        /// Portion of all shares that must vote on grant proposals
+        const GrantQuorum: Permill = T::GrantQuorum::get();
+
+        /// Meta proposal bond
+        const MetaProposalBond: BalanceOf<T> = T::MetaProposalBond::get();
+
+        /// Threshold requirement for meta proposals
+        const MetaConsensusThreshold: Permill = T::MetaConsensusThreshold::get();
+
+        /// Portion of all shares that must vote on meta proposals
+        const MetaQuorum: Permill = T::MetaQuorum::get();
+
+        /// Stale applications and votes are removed at the start of the block and votes with
+        /// conviction whose lock ends are released
+        /// - storage predating `Version::V4` is migrated first, and nothing else happens until
+        /// the migration finishes
+        fn on_initialize(n: T::BlockNumber) {
+            if Self::storage_version() == Version::V1
+                && migration::migrate_to_hashed_proposals::<T>()
+            {
+                StorageVersion::put(Version::V2);
+            }
+            if Self::storage_version() == Version::V2 {
+                migration::migrate_to_share_checkpoints::<T>();
+                StorageVersion::put(Version::V3);
+            }
+            if Self::storage_version() == Version::V3 {
+                migration::migrate_to_parameter_storage::<T>();
+                StorageVersion::put(Version::V4);
+            }
+            if Self::storage_version() != migration::CURRENT_VERSION {
+                return;
+            }
+            Self::expire_proposals(n);
+            Self::unlock_conviction_votes(n);
+        }
+
+        /// Reserved shares from closed votes are released every block in bounded chunks,
+        /// grant payments due at block `n` are made and passed membership proposals are absorbed
+        /// every `BatchPeriod` blocks
+        fn on_finalize(n: T::BlockNumber) {
+            if Self::storage_version() != migration::CURRENT_VERSION {
+                return;
+            }
+            Self::release_closed_vote_shares(T::MaximumVoteReleases::get());
+            Self::pay_grants(n);
+            if (n % Self::batch_period()).is_zero() {
+                Self::execute_membership_approvals();
+            }
+        }
+
         /// Anyone can apply to exchange shares for capital
         /// - any punishment if the application fails and does this depend on how it fails?
         fn membership_application(
@@ -258,34 +842,215 @@ decl_module! {
             shares_requested: Shares,
         ) -> DispatchResult {
             let applicant = ensure_signed(origin)?;
+            Self::ensure_migrated()?;
             // membership criteria (see #27)
             ensure!(
                 stake_promised > T::Currency::minimum_balance(),
                 Error::<T>::InvalidMembershipApplication,
             );
+            T::AdmissionPolicy::admit(&applicant, stake_promised, shares_requested)?;
 
-            // uniform bond until full functionality (see ../collateral for details on future impl)
-            let collateral = T::MembershipProposalBond::get();
+            // scaled by how dilutive the application is (see ../collateral)
+            let collateral = Self::membership_application_bond(stake_promised, shares_requested)?;
             T::Currency::reserve(&applicant, collateral)
                 .map_err(|_| Error::<T>::InsufficientMembershipApplicantCollateral)?;
-            let c = Self::membership_application_count() + 1;
-            MembershipApplicationCount::put(c);
-            let now = <system::Module<T>>::block_number();
-            let membership_app = MembershipProposal {
-                index: c,
-                who: applicant,
+            Self::insert_membership_application(
+                applicant,
+                stake_promised,
+                shares_requested,
+                collateral,
+            );
+            Ok(())
+        }
+
+        /// Anyone can apply to exchange shares for capital raised from backers
+        /// - the applicant reserves a deposit of `MembershipProposalBond` (see `Crowdfund`)
+        /// - backers contribute the application bond and `stake_promised` and split
+        /// `backer_share` of `shares_requested` (see `back_membership_application`)
+        /// - the application can only be sponsored once both are raised in full
+        fn crowdfunded_membership_application(
+            origin,
+            stake_promised: BalanceOf<T>,
+            shares_requested: Shares,
+            backer_share: Permill,
+        ) -> DispatchResult {
+            let applicant = ensure_signed(origin)?;
+            Self::ensure_migrated()?;
+            ensure!(
+                stake_promised > T::Currency::minimum_balance(),
+                Error::<T>::InvalidMembershipApplication,
+            );
+            T::AdmissionPolicy::admit(&applicant, stake_promised, shares_requested)?;
+
+            // raised from backers instead of reserved from the applicant
+            let collateral = Self::membership_application_bond(stake_promised, shares_requested)?;
+            let deposit = Self::membership_proposal_bond();
+            T::Currency::reserve(&applicant, deposit)
+                .map_err(|_| Error::<T>::InsufficientMembershipApplicantCollateral)?;
+            let id = Self::insert_membership_application(
+                applicant,
                 stake_promised,
                 shares_requested,
+                collateral,
+            );
+            let crowdfund = Crowdfund {
+                backer_share,
+                deposit,
+                bond_raised: Zero::zero(),
+                stake_raised: Zero::zero(),
+            };
+            <Crowdfunds<T>>::insert(id, crowdfund);
+            Self::deposit_event(RawEvent::MembershipApplicationCrowdfunded(id, backer_share));
+            Ok(())
+        }
+
+        /// Anyone can back a crowdfunded application with part of its bond and/or stake
+        /// - both are reserved until the application is executed, when the stake moves to the
+        /// bank and the bond is refunded
+        /// - if the application fails, the stake is refunded and the bond is refunded or slashed
+        /// like an applicant's bond
+        fn back_membership_application(
+            origin,
+            id: T::Hash,
+            bond: BalanceOf<T>,
+            stake: BalanceOf<T>,
+        ) -> DispatchResult {
+            let backer = ensure_signed(origin)?;
+            Self::ensure_migrated()?;
+            ensure!(!(bond.is_zero() && stake.is_zero()), Error::<T>::EmptyContribution);
+            let membership_proposal = <MembershipApplications<T>>::get(id)
+                .ok_or(Error::<T>::IndexWithNoAssociatedMembershipProposal)?;
+            ensure!(
+                membership_proposal.stage == ProposalStage::Application,
+                Error::<T>::RequestInWrongStage
+            );
+            let crowdfund =
+                <Crowdfunds<T>>::get(id).ok_or(Error::<T>::NotACrowdfundedApplication)?;
+            let crowdfund = Crowdfund {
+                bond_raised: crowdfund.bond_raised.saturating_add(bond),
+                stake_raised: crowdfund.stake_raised.saturating_add(stake),
+                ..crowdfund
+            };
+            ensure!(
+                crowdfund.bond_raised <= membership_proposal.bond
+                    && crowdfund.stake_raised <= membership_proposal.stake_promised,
+                Error::<T>::ContributionExceedsRemaining
+            );
+
+            T::Currency::reserve(&backer, bond.saturating_add(stake))
+                .map_err(|_| Error::<T>::InsufficientBackerCollateral)?;
+            <Contributions<T>>::mutate(id, |contributions| {
+                match contributions.iter_mut().find(|(who, _, _)| who == &backer) {
+                    Some((_, bonded, staked)) => {
+                        *bonded = bonded.saturating_add(bond);
+                        *staked = staked.saturating_add(stake);
+                    }
+                    None => contributions.push((backer.clone(), bond, stake)),
+                }
+            });
+            let funded = crowdfund.is_funded(
+                &membership_proposal.bond,
+                &membership_proposal.stake_promised,
+            );
+            <Crowdfunds<T>>::insert(id, crowdfund);
+            Self::deposit_event(RawEvent::MembershipApplicationBacked(id, backer, bond, stake));
+            if funded {
+                Self::deposit_event(RawEvent::MembershipApplicationFunded(id));
+            }
+            Ok(())
+        }
+
+        /// Anyone can apply for a grant of `amount` paid out of the bank to `recipient`
+        /// - the grant is sponsored and voted on like any other proposal and paid according to
+        /// its `schedule` once it passes
+        fn grant_application(
+            origin,
+            recipient: T::AccountId,
+            amount: BalanceOf<T>,
+            schedule: GrantSchedule<T::BlockNumber>,
+        ) -> DispatchResult {
+            let applicant = ensure_signed(origin)?;
+            Self::ensure_migrated()?;
+            ensure!(!amount.is_zero(), Error::<T>::InvalidGrantApplication);
+            if let GrantSchedule::Installments { payments, period } = &schedule {
+                ensure!(
+                    !payments.is_zero() && !period.is_zero(),
+                    Error::<T>::InvalidGrantApplication
+                );
+            }
+
+            let collateral = T::GrantProposalBond::get();
+            T::Currency::reserve(&applicant, collateral)
+                .map_err(|_| Error::<T>::InsufficientGrantApplicantCollateral)?;
+            let nonce = Self::grant_application_nonce(&applicant);
+            // unique because the applicant's nonce is part of it
+            let id = Self::grant_proposal_id(&applicant, &recipient, amount, &schedule, nonce);
+            <GrantApplicationNonces<T>>::insert(&applicant, nonce + 1);
+            let now = <system::Module<T>>::block_number();
+            let grant_app = GrantProposal {
+                who: applicant,
+                recipient: recipient.clone(),
+                amount,
+                schedule,
+                payments_made: 0,
+                bond: collateral,
+                stage: ProposalStage::Application,
+                time_proposed: now,
+            };
+            <GrantApplications<T>>::insert(id, grant_app);
+            <ProposalExpiries<T>>::mutate(now + T::ApplicationTimeLimit::get(), |v| v.push(id));
+
+            Self::deposit_event(RawEvent::GrantApplicationProposed(id, recipient, amount, now));
+            Ok(())
+        }
+
+        /// Members propose a change to one of the economic parameters
+        /// - the proposal is sponsored and voted on like any other proposal and the change
+        /// takes effect as soon as it passes
+        fn meta_proposal(origin, change: ParameterChangeOf<T>) -> DispatchResult {
+            let proposer = ensure_signed(origin)?;
+            Self::ensure_migrated()?;
+            ensure!(Self::is_member(&proposer), Error::<T>::NotAMember);
+            Self::ensure_valid_parameter_change(&change)?;
+
+            let collateral = T::MetaProposalBond::get();
+            T::Currency::reserve(&proposer, collateral)
+                .map_err(|_| Error::<T>::InsufficientMetaProposerCollateral)?;
+            let nonce = Self::meta_proposal_nonce(&proposer);
+            // unique because the proposer's nonce is part of it
+            let id = Self::meta_proposal_id(&proposer, &change, nonce);
+            <MetaProposalNonces<T>>::insert(&proposer, nonce + 1);
+            let now = <system::Module<T>>::block_number();
+            let meta_proposal = MetaProposal {
+                who: proposer.clone(),
+                change,
+                bond: collateral,
                 stage: ProposalStage::Application,
                 time_proposed: now,
             };
-            // Deprecated until #7 is pursued
-            // <MembershipApplicationQ<T>>::mutate(|v| v.push(membership_app.clone()));
-            <MembershipApplications<T>>::insert(c, membership_app);
+            <MetaProposals<T>>::insert(id, meta_proposal);
+            <ProposalExpiries<T>>::mutate(now + T::ApplicationTimeLimit::get(), |v| v.push(id));
 
-            Self::deposit_event(RawEvent::MembershipApplicationProposed(
-                c, stake_promised, shares_requested, now)
+            Self::deposit_event(RawEvent::MetaProposalProposed(id, proposer, now));
+            Ok(())
+        }
+
+        /// Applicants withdraw applications that haven't been sponsored yet
+        /// - the application bond is refunded in full
+        fn withdraw_membership_application(origin, id: T::Hash) -> DispatchResult {
+            let applicant = ensure_signed(origin)?;
+            Self::ensure_migrated()?;
+            let membership_proposal = <MembershipApplications<T>>::get(id)
+                .ok_or(Error::<T>::IndexWithNoAssociatedMembershipProposal)?;
+            ensure!(membership_proposal.who == applicant, Error::<T>::NotTheApplicant);
+            ensure!(
+                membership_proposal.stage == ProposalStage::Application,
+                Error::<T>::CannotWithdrawSponsoredApplication
             );
+
+            <MembershipApplications<T>>::remove(id);
+            Self::refund_application_bond(id, &membership_proposal);
+            Self::deposit_event(RawEvent::MembershipApplicationWithdrawn(id, applicant));
             Ok(())
         }
 
@@ -295,265 +1060,1171 @@ decl_module! {
         ///		- `max_share_bond` exists so that UI's estimate isn't too wrong and it fucks over sponsors
         ///		- any punishment if the sponsored proposal is rejected?
         /// - note that someone could sponsor their own application
-        /// - (1), (2) and (3) should be reordered s.t. the first check panics the most often, thereby
-        /// limiting computational costs in the event of panics
-        fn sponsor_membership_application(origin, index: ProposalIndex) -> DispatchResult {
+        /// - the sponsor chooses the `VoteWeighting` of the vote it opens
+        /// - works on every `ProposalKind`, whose `Proposal` impl sets the bonds and threshold
+        fn sponsor_proposal(origin, id: T::Hash, weighting: VoteWeighting) -> DispatchResult {
             let sponsor = ensure_signed(origin)?;
-            ensure!(Self::is_member(&sponsor), Error::<T>::NotAMember);
-
-            // (1)
-            let wrapped_membership_proposal = <MembershipApplications<T>>::get(index);
-            ensure!(wrapped_membership_proposal.is_some(), Error::<T>::IndexWithNoAssociatedMembershipProposal);
-            let membership_proposal = wrapped_membership_proposal.expect("just checked above; qed");
-            ensure!(membership_proposal.stage == ProposalStage::Application, Error::<T>::RequestInWrongStage);
-
-            let sponsor_bond = T::MembershipSponsorBond::get();
-
-            // (3) check if the sponsor has enough to afford the sponsor bond by using `ShareProfile`
-            let sponsor_share_profile = <MembershipShares<T>>::get(&sponsor).expect("invariant i: all members must have some shares and therefore some item in the shares map");
-            ensure!(sponsor_share_profile.can_reserve(sponsor_bond), Error::<T>::InsufficientMembershipSponsorCollateral);
-            let new_reserved = sponsor_share_profile.reserved_shares + sponsor_bond;
-            let new_share_profile = ShareProfile {
-                reserved_shares: new_reserved,
-                total_shares: sponsor_share_profile.total_shares,
-            };
-            <MembershipShares<T>>::insert(&sponsor, new_share_profile);
-
-            /// Sponsorship is default treated like a vote in the amount of `sponsor_bond` (up for discussion, see #22)
-            let sponsor_vote_in_favor = Vote::InFavor(sponsor_bond);
-            <VotesByMembers<T>>::insert(index, &sponsor, sponsor_vote_in_favor);
-
-            // instantiate a membership vote here
-            let vote_state = MembershipVotingState {
-                in_favor: sponsor_bond,
-                against: 0u32,
-                // TODO: GET THE SHARE COUNT AND PLACE HERE
-                // - ADD NOTE ON VOTER REGISTRATION PROS/CONS AND WHAT IT HAS TO DO WITH SPONSOR BOND QUESTIONS
-                all_voters: 1u32,
-                // TODO: this should depend on the type of proposal (grant, membership, meta) `=>`
-                // ...matters once we bring in `ColoredProposal`s
-                threshold: VoteThreshold::SimpleMajority,
-            };
-            // initialize the membership vote
-            <MembershipVoteStates>::insert(index, vote_state);
-            /// Share reservation data is updated in `MembershipShares` map
-
-            /// Adjust the membership proposal in `MemberApplication`s so it isn't purged
-            let voting_membership_proposal = MembershipProposal {
-                stage: ProposalStage::Voting,
-                ..membership_proposal
-            };
-            <MembershipApplications<T>>::insert(
-                membership_proposal.index, voting_membership_proposal
-            );
-
-            Self::deposit_event(
-                RawEvent::MembershipApplicationSponsored(
-                    index,
-                    sponsor_bond,
-                    membership_proposal.stake_promised,
-                    membership_proposal.shares_requested
-                )
-            );
-            Ok(())
+            Self::ensure_migrated()?;
+            match ProposalKind::of::<T>(id).ok_or(Error::<T>::IndexWithNoAssociatedProposal)? {
+                ProposalKind::Membership => {
+                    Self::sponsor::<MembershipProposalOf<T>>(sponsor, id, weighting)
+                }
+                ProposalKind::Grant => Self::sponsor::<GrantProposalOf<T>>(sponsor, id, weighting),
+                ProposalKind::Meta => Self::sponsor::<MetaProposalOf<T>>(sponsor, id, weighting),
+            }
         }
 
         /// Voting Method
         /// - add docs based on issues #17 and #18
-        fn vote_on_membership(origin, index: ProposalIndex, vote: Vote) -> DispatchResult {
+        /// - a vote of the same kind as the member's existing vote (in favor, against or abstain)
+        /// adds to it and any other vote replaces it
+        /// - `conviction` multiplies the vote's weight and applies to the whole vote when an
+        /// existing vote is changed (it can't be lowered)
+        /// - works on every `ProposalKind`
+        fn vote_on_proposal(origin, id: T::Hash, vote: Vote, conviction: Conviction) -> DispatchResult {
             let voter = ensure_signed(origin)?;
-            ensure!(Self::is_member(&voter), Error::<T>::NotAMember);
+            Self::ensure_migrated()?;
+            match ProposalKind::of::<T>(id).ok_or(Error::<T>::IndexWithNoAssociatedProposal)? {
+                ProposalKind::Membership => {
+                    Self::vote::<MembershipProposalOf<T>>(voter, id, vote, conviction)
+                }
+                ProposalKind::Grant => Self::vote::<GrantProposalOf<T>>(voter, id, vote, conviction),
+                ProposalKind::Meta => Self::vote::<MetaProposalOf<T>>(voter, id, vote, conviction),
+            }
+        }
 
-            let wrapped_membership_proposal = <MembershipApplications<T>>::get(index);
-            ensure!(
-                wrapped_membership_proposal.is_some(),
-                Error::<T>::IndexWithNoAssociatedMembershipProposal
-            );
-            let membership_proposal = wrapped_membership_proposal.expect("just checked inner existence above; qed");
-            ensure!(
-                membership_proposal.stage == ProposalStage::Voting, Error::<T>::RequestInWrongStage
-            );
+        /// Remove the member's vote from an open proposal
+        /// - reverses the vote's effect on the tally and releases its reserved shares right away
+        /// (including those drawn from delegators) because the vote no longer counts
+        /// - the sponsor's vote backs the sponsorship and can't be retracted
+        fn retract_vote(origin, id: T::Hash) -> DispatchResult {
+            let voter = ensure_signed(origin)?;
+            Self::ensure_migrated()?;
+            match ProposalKind::of::<T>(id).ok_or(Error::<T>::IndexWithNoAssociatedProposal)? {
+                ProposalKind::Membership => Self::retract::<MembershipProposalOf<T>>(voter, id),
+                ProposalKind::Grant => Self::retract::<GrantProposalOf<T>>(voter, id),
+                ProposalKind::Meta => Self::retract::<MetaProposalOf<T>>(voter, id),
+            }
+        }
 
-            let direction = vote.is_in_favor();
-            let magnitude = vote.inner();
-            // the vote bond's is `T::MembershipVoteBond` but it reserves the magnitude of the vote (=> the minimum vote amount is `T::MembershipVoteBond`)
-            ensure!(
-                magnitude >= T::MembershipVoteBond::get(), Error::<T>::VoteMagnitudeBelowMinimumVoteBond
-            );
+        /// Lend the voting weight of `amount` shares to another member
+        /// - the shares stay with the delegator and are only reserved (on the delegator's
+        /// `ShareProfile`) when the delegate votes with them
+        /// - delegation is a single hop: delegated weight can only be used by the delegate's own
+        /// votes and is never passed along by the delegate's own delegation
+        fn delegate_shares(origin, to: T::AccountId, amount: Shares) -> DispatchResult {
+            let delegator = ensure_signed(origin)?;
+            Self::ensure_migrated()?;
+            ensure!(Self::is_member(&delegator), Error::<T>::NotAMember);
+            ensure!(delegator != to, Error::<T>::CannotDelegateToSelf);
+            ensure!(Self::is_member(&to), Error::<T>::DelegateNotAMember);
+            ensure!(!amount.is_zero(), Error::<T>::InvalidDelegation);
+            ensure!(!<Delegations<T>>::exists(&delegator), Error::<T>::AlreadyDelegated);
+            let share_profile = <MembershipShares<T>>::get(&delegator).ok_or(Error::<T>::NoMembershipShareInfo)?;
+            ensure!(share_profile.can_reserve(amount), Error::<T>::InsufficientUnreservedShares);
+
+            let delegation = Delegation {
+                delegate: to.clone(),
+                shares: amount,
+                drawn: 0,
+            };
+            <Delegations<T>>::insert(&delegator, delegation);
+            <DelegatorsOf<T>>::mutate(&to, |delegators| delegators.push(delegator.clone()));
+            Self::deposit_event(RawEvent::SharesDelegated(delegator, to, amount));
+            Ok(())
+        }
+
+        /// Take back delegated voting weight
+        /// - fails while the delegate's votes on open proposals reserve any of the delegated shares
+        fn undelegate(origin) -> DispatchResult {
+            let delegator = ensure_signed(origin)?;
+            Self::ensure_migrated()?;
+            let delegation = <Delegations<T>>::get(&delegator).ok_or(Error::<T>::NoDelegation)?;
+            ensure!(delegation.drawn.is_zero(), Error::<T>::DelegatedSharesInUse);
+            Self::remove_delegation(&delegator, &delegation.delegate);
+            Self::deposit_event(RawEvent::SharesUndelegated(delegator, delegation.delegate));
+            Ok(())
+        }
+
+        /// Members burn shares in exchange for their proportional share of the bank's capital
+        /// - only unreserved shares may be burned so members can't exit while their shares
+        /// are still backing open votes or sponsorships
+        /// - members that burn all of their shares leave the organization
+        fn leave_membership(origin, shares: Shares) -> DispatchResult {
+            let leaver = ensure_signed(origin)?;
+            Self::ensure_migrated()?;
+            ensure!(Self::is_member(&leaver), Error::<T>::NotAMember);
+            ensure!(!shares.is_zero(), Error::<T>::InvalidShareBurn);
+
+            let share_profile = <MembershipShares<T>>::get(&leaver).ok_or(Error::<T>::NoMembershipShareInfo)?;
+            ensure!(share_profile.can_reserve(shares), Error::<T>::InsufficientUnreservedShares);
+
+            let mut bank = <BankAccount<T>>::get();
+            let payout = Self::capital_for_shares(shares, bank.clone())?;
+            T::Currency::transfer(&Self::account_id(), &leaver, payout, ExistenceRequirement::KeepAlive)?;
+            bank.buyback(shares);
+            let total_shares = bank.shares;
+            <BankAccount<T>>::put(bank);
+
+            let remaining_shares = share_profile.total_shares - shares;
+            Self::checkpoint_shares(&leaver, remaining_shares);
+            Self::deposit_event(RawEvent::MembershipSharesBurned(leaver.clone(), shares, payout));
+            if remaining_shares.is_zero() {
+                // no shares are reserved so none of the delegated shares are drawn
+                if let Some(delegation) = <Delegations<T>>::get(&leaver) {
+                    Self::remove_delegation(&leaver, &delegation.delegate);
+                }
+                <MembershipShares<T>>::remove(&leaver);
+                <Members<T>>::mutate(|members| members.retain(|m| m != &leaver));
+                Self::deposit_event(RawEvent::MemberLeft(leaver));
+            } else {
+                let new_share_profile = ShareProfile {
+                    total_shares: remaining_shares,
+                    ..share_profile
+                };
+                <MembershipShares<T>>::insert(&leaver, new_share_profile);
+            }
+            // after the leaver's shares are updated so electorate weights count what remains
+            Self::update_open_vote_electorates(total_shares);
+            Ok(())
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// Ok once storage is in the current layout, since parameters and proposal ids are only in
+    /// place after every migration ran (see `migration.rs`)
+    fn ensure_migrated() -> DispatchResult {
+        ensure!(
+            Self::storage_version() == migration::CURRENT_VERSION,
+            Error::<T>::StorageMigrationInProgress
+        );
+        Ok(())
+    }
+
+    /// Membership checking supporting a single member
+    pub fn is_member(who: &T::AccountId) -> bool {
+        <Members<T>>::get().contains(who)
+    }
+
+    /// Proposal id of a grant application
+    /// - tagged with `b"grant"` so it can't collide with another kind's id (see `ProposalKind::of`)
+    /// - the `nonce` is the number of grant applications previously made by `who`
+    /// (see `GrantApplicationNonces`)
+    pub fn grant_proposal_id(
+        who: &T::AccountId,
+        recipient: &T::AccountId,
+        amount: BalanceOf<T>,
+        schedule: &GrantSchedule<T::BlockNumber>,
+        nonce: u32,
+    ) -> T::Hash {
+        T::Hashing::hash_of(&(b"grant", who, recipient, amount, schedule, nonce))
+    }
+
+    /// Proposal id of a meta proposal
+    /// - tagged with `b"meta"` so it can't collide with another kind's id (see `ProposalKind::of`)
+    /// - the `nonce` is the number of meta proposals previously made by `who`
+    /// (see `MetaProposalNonces`)
+    pub fn meta_proposal_id(
+        who: &T::AccountId,
+        change: &ParameterChangeOf<T>,
+        nonce: u32,
+    ) -> T::Hash {
+        T::Hashing::hash_of(&(b"meta", who, change, nonce))
+    }
+
+    /// Set the parameter changed by a passed meta proposal and record it in `ParameterHistory`
+    /// Ok if `change` leaves every kind of proposal able to pass
+    /// - the sponsor and vote bonds can't exceed the shares of the largest member or nobody
+    /// could sponsor or vote
+    /// - `MembershipConsensusThreshold` can't exceed `MetaConsensusThreshold` so that meta
+    /// proposals stay at least as hard to pass as membership proposals
+    fn ensure_valid_parameter_change(change: &ParameterChangeOf<T>) -> Result<(), Error<T>> {
+        let largest_holding = || {
+            <Members<T>>::get()
+                .iter()
+                .filter_map(|member| <MembershipShares<T>>::get(member))
+                .map(|profile| profile.total_shares)
+                .max()
+                .unwrap_or(0)
+        };
+        let valid = match change {
+            ParameterChange::MembershipProposalBond(bond) => !bond.is_zero(),
+            ParameterChange::MembershipSponsorBond(bond)
+            | ParameterChange::MembershipVoteBond(bond) => {
+                !bond.is_zero() && *bond <= largest_holding()
+            }
+            ParameterChange::MaximumShareIssuance(issuance) => !issuance.is_zero(),
+            ParameterChange::MembershipConsensusThreshold(threshold) => {
+                !threshold.is_zero() && *threshold <= T::MetaConsensusThreshold::get()
+            }
+            ParameterChange::BatchPeriod(period) => !period.is_zero(),
+        };
+        ensure!(valid, Error::<T>::InvalidParameterChange);
+        Ok(())
+    }
+
+    fn change_parameter(change: ParameterChangeOf<T>) {
+        match change.clone() {
+            ParameterChange::MembershipProposalBond(bond) => <MembershipProposalBond<T>>::put(bond),
+            ParameterChange::MembershipSponsorBond(bond) => MembershipSponsorBond::put(bond),
+            ParameterChange::MembershipVoteBond(bond) => MembershipVoteBond::put(bond),
+            ParameterChange::MaximumShareIssuance(issuance) => MaximumShareIssuance::put(issuance),
+            ParameterChange::MembershipConsensusThreshold(threshold) => {
+                MembershipConsensusThreshold::put(threshold)
+            }
+            ParameterChange::BatchPeriod(period) => <BatchPeriod<T>>::put(period),
+        }
+        let now = <system::Module<T>>::block_number();
+        <ParameterHistory<T>>::mutate(|history| history.push((now, change)));
+    }
+
+    /// The bank's shares to capital ratio, which applications are compared with
+    /// - a bank without shares has no ratio of its own (every shares per capital would be
+    /// infinitely dilutive next to it) so its base rate is `ConversionRate::parity`
+    pub fn banks_conversion_rate() -> Result<ConversionRate, Error<T>> {
+        let bank = Self::bank_account();
+        if bank.shares.is_zero() {
+            return Ok(ConversionRate::parity());
+        }
+        Ok(Self::conversion_comparison(
+            bank.shares,
+            Self::bank_balance(bank)?,
+        ))
+    }
+
+    /// The application bond for applying with `stake_promised` for `shares_requested`
+    /// - `MembershipProposalBond` scaled by how the application's shares to capital ratio
+    /// compares with the bank's (see `calculate_collateral_req`)
+    pub fn membership_application_bond(
+        stake_promised: BalanceOf<T>,
+        shares_requested: Shares,
+    ) -> Result<BalanceOf<T>, Error<T>> {
+        let banks_rate = Self::banks_conversion_rate()?;
+        let rate = Self::conversion_comparison(shares_requested, stake_promised);
+        let base = Self::membership_proposal_bond();
+        match Self::calculate_collateral_req(BondType::Applicant(base), rate, banks_rate) {
+            BondType::Applicant(bond) => Ok(bond),
+            // `calculate_collateral_req` keeps the bond type
+            _ => Ok(base),
+        }
+    }
+
+    /// Store a new membership application whose `bond` is reserved or raised by the caller
+    /// - returns the proposal id (see `membership_proposal_id`), which is unique because the
+    /// applicant's nonce is part of it
+    /// - applying again with the same terms makes another application, so runtimes that reject
+    /// duplicate applications do so with `admission::OneOpenApplication`
+    fn insert_membership_application(
+        applicant: T::AccountId,
+        stake_promised: BalanceOf<T>,
+        shares_requested: Shares,
+        bond: BalanceOf<T>,
+    ) -> T::Hash {
+        let nonce = Self::membership_application_nonce(&applicant);
+        let id = Self::membership_proposal_id(&applicant, stake_promised, shares_requested, nonce);
+        <MembershipApplicationNonces<T>>::insert(&applicant, nonce + 1);
+        <LatestMembershipApplication<T>>::insert(&applicant, id);
+        let c = Self::membership_application_count() + 1;
+        MembershipApplicationCount::put(c);
+        let now = <system::Module<T>>::block_number();
+        let membership_app = MembershipProposal {
+            who: applicant,
+            stake_promised,
+            shares_requested,
+            bond,
+            stage: ProposalStage::Application,
+            time_proposed: now,
+        };
+        <MembershipApplications<T>>::insert(id, membership_app);
+        <ProposalExpiries<T>>::mutate(now + T::ApplicationTimeLimit::get(), |v| v.push(id));
+
+        Self::deposit_event(RawEvent::MembershipApplicationProposed(
+            id,
+            stake_promised,
+            shares_requested,
+            now,
+        ));
+        id
+    }
+
+    /// Proposal id of a membership application
+    /// - tagged with `b"membership"` so it can't collide with another kind's id
+    /// (see `ProposalKind::of`)
+    /// - clients can compute the id before submitting because the `nonce` is the number of
+    /// applications previously made by `who` (see `MembershipApplicationNonces`)
+    pub fn membership_proposal_id(
+        who: &T::AccountId,
+        stake_promised: BalanceOf<T>,
+        shares_requested: Shares,
+        nonce: u32,
+    ) -> T::Hash {
+        T::Hashing::hash_of(&(b"membership", who, stake_promised, shares_requested, nonce))
+    }
 
-            // Get Membership Voting State to verify valid transition before updating it
-            let wrapped_vote_by_member = <VotesByMembers<T>>::get(index, &voter);
-
-            // get member share profile { reserved_shares, total_shares }
-            let voter_share_profile = <MembershipShares<T>>::get(&voter).ok_or(Error::<T>::NoMembershipShareInfo)?;
-
-            let (mut new, mut same, mut different) = (false, false, false);
-            let mut new_reserved = voter_share_profile.reserved_shares;
-            let mut new_magnitude = magnitude;
-            // all of these variable initializations are designed to be overshadowed
-            let mut shares_imbalance_sign: bool = false;
-            let mut shares_imbalance: Shares = 0;
-            let mut less_shares_reserved: bool = false;
-            let mut difference: Shares = 0;
-            match wrapped_vote_by_member {
-                // (1) there does not already exist a vote by this member on this proposal
-                None => new = true,
-                // (2) vote exists and it's the same direction as the new vote
-                Some(Vote::InFavor(shares)) if direction => {
-                    same = true;
-                    // must aggregate here because we don't have access
-                    // to shares outside of this match statement
-                    new_magnitude += shares;
-                },
-                Some(Vote::Against(shares)) if !direction => {
-                    same = true;
-                    // must aggregate here because we don't have access to shares
-                    // outside of this match statement
-                    new_magnitude += shares;
-                },
-                // (3)vote exists and it's the opposite direction as the new vote
-                // (Some(Vote::InFavor(shares)) if !direction || (
-                // Some(Vote::Against(shares)) if direction)
-                Some(Vote::InFavor(shares)) if !direction => {
-                    different = true;
-                    // increment negative shares imbalance
-                    // (and decrease from <MembershipVote<T>>::get(account).in_favor)
-                    // false for negative sign (negative share imbalance)
-                    shares_imbalance_sign = false;
-                    // add the shares amount to the imbalance
-                    shares_imbalance = shares;
-
-                    less_shares_reserved = new_magnitude <= shares;
-                    if less_shares_reserved {
-                        difference = shares - new_magnitude;
+    /// Reserve the sponsor bond of the proposal of kind `P` stored under `id` and open it to
+    /// voting
+    /// - (1), (2) and (3) should be reordered s.t. the first check panics the most often, thereby
+    /// limiting computational costs in the event of panics
+    fn sponsor<P: Proposal<T>>(
+        sponsor: T::AccountId,
+        id: T::Hash,
+        weighting: VoteWeighting,
+    ) -> DispatchResult {
+        ensure!(Self::is_member(&sponsor), Error::<T>::NotAMember);
+
+        // (1)
+        let proposal = P::get(id).ok_or(Error::<T>::IndexWithNoAssociatedProposal)?;
+        ensure!(
+            proposal.stage() == &ProposalStage::Application,
+            Error::<T>::RequestInWrongStage
+        );
+        proposal.ensure_sponsorable(id)?;
+
+        let sponsor_bond = proposal.sponsor_bond();
+
+        // (3) check if the sponsor has enough to afford the sponsor bond by using `ShareProfile`
+        let sponsor_share_profile = <MembershipShares<T>>::get(&sponsor).expect(
+            "invariant i: all members must have some shares and therefore some item in the shares map",
+        );
+        ensure!(
+            sponsor_share_profile.can_reserve(sponsor_bond),
+            Error::<T>::InsufficientMembershipSponsorCollateral
+        );
+        let new_reserved = sponsor_share_profile.reserved_shares + sponsor_bond;
+        let new_share_profile = ShareProfile {
+            reserved_shares: new_reserved,
+            total_shares: sponsor_share_profile.total_shares,
+        };
+        <MembershipShares<T>>::insert(&sponsor, new_share_profile);
+
+        // Sponsorship is default treated like a vote in the amount of `sponsor_bond` (up for discussion, see #22)
+        let sponsor_vote_in_favor = Vote::InFavor(sponsor_bond);
+        <VotesByMembers<T>>::insert(id, &sponsor, sponsor_vote_in_favor);
+        <Voters<T>>::mutate(id, |voters| voters.push(sponsor.clone()));
+
+        // instantiate the vote here
+        let vote_state = VotingState {
+            in_favor: weighting.weight(sponsor_bond),
+            against: 0u32,
+            turnout: sponsor_bond,
+            // every share can vote (TODO: ADD NOTE ON VOTER REGISTRATION PROS/CONS AND WHAT IT HAS
+            // TO DO WITH SPONSOR BOND QUESTIONS)
+            all_voters: <BankAccount<T>>::get().shares,
+            electorate_weight: Self::electorate_weight(weighting),
+            // recorded so that later changes to the threshold parameters don't affect open votes
+            threshold: proposal.threshold(),
+            weighting,
+        };
+        <VoteStates<T>>::insert(id, vote_state);
+        <OpenVotes<T>>::mutate(|open| open.push(id));
+        let now = <system::Module<T>>::block_number();
+        // shares issued later in this block (i.e. by a batch in `on_finalize`) can't vote
+        <VoteSnapshots<T>>::insert(id, now.saturating_sub(One::one()));
+        let voting_ends = now + T::VotingPeriod::get();
+        <VoteExpiries<T>>::mutate(voting_ends, |v| v.push(id));
+
+        proposal.on_sponsored(id, sponsor_bond);
+        // move the proposal to voting so it isn't purged
+        proposal.with_stage(ProposalStage::Voting).insert(id);
+        Ok(())
+    }
+
+    /// Apply `voter`'s vote to the open vote on the proposal of kind `P` stored under `id`
+    fn vote<P: Proposal<T>>(
+        voter: T::AccountId,
+        id: T::Hash,
+        vote: Vote,
+        conviction: Conviction,
+    ) -> DispatchResult {
+        ensure!(Self::is_member(&voter), Error::<T>::NotAMember);
+
+        let proposal = P::get(id).ok_or(Error::<T>::IndexWithNoAssociatedProposal)?;
+        ensure!(
+            proposal.stage() == &ProposalStage::Voting,
+            Error::<T>::RequestInWrongStage
+        );
+
+        let magnitude = vote.inner();
+        // a vote reserves its whole magnitude, which must be at least the proposal's vote bond
+        ensure!(
+            magnitude >= proposal.vote_bond(),
+            Error::<T>::VoteMagnitudeBelowMinimumVoteBond
+        );
+
+        // Get Membership Voting State to verify valid transition before updating it
+        let old_vote = <VotesByMembers<T>>::get(id, &voter);
+        let old_conviction = <VoteConvictions<T>>::get(id, &voter);
+        let vote_state = <VoteStates<T>>::get(id).ok_or(Error::<T>::VoteStateUninitialized)?;
+        let new_vote = Vote::combine(old_vote.as_ref(), vote.clone()).map_err(Error::<T>::from)?;
+        // the sponsor is always the first voter and votes in favor of at least the sponsor bond,
+        // which adding to the vote in favor can't lower
+        if <Voters<T>>::get(id).first() == Some(&voter) {
+            let in_favor = match new_vote {
+                Vote::InFavor(_) => true,
+                _ => false,
+            };
+            ensure!(in_favor, Error::<T>::CannotReplaceSponsorVote);
+        }
+        let (new_vote_state, share_delta) = vote_state
+            .apply(
+                old_vote.clone().map(|old| (old, old_conviction)),
+                (vote, conviction),
+            )
+            .map_err(Error::<T>::from)?;
+        let old_shares = old_vote.as_ref().map(Vote::inner).unwrap_or(0);
+        Self::reserve_vote_shares(id, &voter, old_shares, share_delta.applied_to(old_shares))?;
+
+        if old_vote.is_none() {
+            <Voters<T>>::mutate(id, |voters| voters.push(voter.clone()));
+        }
+        <VotesByMembers<T>>::insert(id, &voter, &new_vote);
+        if conviction == Conviction::None {
+            <VoteConvictions<T>>::remove(id, &voter);
+        } else {
+            <VoteConvictions<T>>::insert(id, &voter, conviction);
+        }
+        // the vote stays open until its `VotingPeriod` ends (see `expire_vote`)
+        <VoteStates<T>>::insert(id, new_vote_state);
+        Self::deposit_event(RawEvent::Voted(id, voter, new_vote, conviction));
+        Ok(())
+    }
+
+    /// Remove `voter`'s vote from the open vote on the proposal of kind `P` stored under `id`
+    fn retract<P: Proposal<T>>(voter: T::AccountId, id: T::Hash) -> DispatchResult {
+        let proposal = P::get(id).ok_or(Error::<T>::IndexWithNoAssociatedProposal)?;
+        ensure!(
+            proposal.stage() == &ProposalStage::Voting,
+            Error::<T>::RequestInWrongStage
+        );
+        let vote = <VotesByMembers<T>>::get(id, &voter).ok_or(Error::<T>::NoVoteToRetract)?;
+        let voters = <Voters<T>>::get(id);
+        // the sponsor is always the first voter
+        ensure!(
+            voters.first() != Some(&voter),
+            Error::<T>::CannotRetractSponsorVote
+        );
+        let vote_state = <VoteStates<T>>::get(id).ok_or(Error::<T>::VoteStateUninitialized)?;
+        let conviction = <VoteConvictions<T>>::get(id, &voter);
+        // all of the vote's shares are released
+        let (vote_state, _) = vote_state
+            .retract((vote.clone(), conviction))
+            .map_err(Error::<T>::from)?;
+
+        <VoteConvictions<T>>::remove(id, &voter);
+        <VotesByMembers<T>>::remove(id, &voter);
+        <Voters<T>>::insert(
+            id,
+            voters
+                .into_iter()
+                .filter(|v| v != &voter)
+                .collect::<Vec<_>>(),
+        );
+        Self::release_vote_shares(id, &voter, vote.inner());
+        Self::deposit_event(RawEvent::VoteRetracted(id, voter));
+        <VoteStates<T>>::insert(id, vote_state);
+        Ok(())
+    }
+
+    /// Remove applications and close votes whose deadline is block `n`
+    /// - unsponsored applications are removed and their bonds refunded
+    /// - proposals still in `ProposalStage::Voting` are decided against their electorate at
+    /// block `n`, which may have changed since the last vote (see `update_open_vote_electorates`)
+    fn expire_proposals(n: T::BlockNumber) {
+        for id in <ProposalExpiries<T>>::take(n) {
+            match ProposalKind::of::<T>(id) {
+                Some(ProposalKind::Membership) => {
+                    Self::expire_application::<MembershipProposalOf<T>>(id)
+                }
+                Some(ProposalKind::Grant) => Self::expire_application::<GrantProposalOf<T>>(id),
+                Some(ProposalKind::Meta) => Self::expire_application::<MetaProposalOf<T>>(id),
+                None => (),
+            }
+        }
+        for id in <VoteExpiries<T>>::take(n) {
+            match ProposalKind::of::<T>(id) {
+                Some(ProposalKind::Membership) => Self::expire_vote::<MembershipProposalOf<T>>(id),
+                Some(ProposalKind::Grant) => Self::expire_vote::<GrantProposalOf<T>>(id),
+                Some(ProposalKind::Meta) => Self::expire_vote::<MetaProposalOf<T>>(id),
+                None => (),
+            }
+        }
+    }
+
+    /// Remove the proposal of kind `P` stored under `id` if it still awaits sponsorship
+    fn expire_application<P: Proposal<T>>(id: T::Hash) {
+        if let Some(proposal) = P::get(id) {
+            if proposal.stage() == &ProposalStage::Application {
+                proposal.on_expired(id);
+            }
+        }
+    }
+
+    /// Close the vote on the proposal of kind `P` stored under `id` if it is still open
+    /// - the proposal passes if it meets its threshold and is rejected otherwise; votes are only
+    /// decided here so that the first few voters can't decide the outcome before the rest of the
+    /// members vote
+    fn expire_vote<P: Proposal<T>>(id: T::Hash) {
+        if let Some(proposal) = P::get(id) {
+            if proposal.stage() == &ProposalStage::Voting {
+                if let Some(vote_state) = <VoteStates<T>>::get(id) {
+                    Self::close_vote(id);
+                    if vote_state.approved() {
+                        proposal.on_passed(id, &vote_state);
                     } else {
-                        difference = new_magnitude - shares;
+                        proposal.on_rejected(id, &vote_state);
                     }
-                    // change total_turnout based on difference
-                },
-                Some(Vote::Against(shares)) if direction => {
-                    different = true;
-                    // increment negative shares imbalance
-                    // (and decrease from <MembershipVote<T>>::get(account).in_favor)
-                    // true for positive sign (positive share imbalance)
-                    shares_imbalance_sign = true;
-                    // add the shares amount to the imbalance
-                    shares_imbalance = shares;
-
-                    less_shares_reserved = new_magnitude <= shares;
-                    if less_shares_reserved {
-                        difference = shares - new_magnitude;
-                    } else {
-                        difference = new_magnitude - shares;
+                }
+            }
+        }
+    }
+
+    /// Queue the reserved shares of all voters on a closed proposal for release
+    /// - called once a proposal leaves `ProposalStage::Voting`, whatever the outcome
+    fn close_vote(id: T::Hash) {
+        <OpenVotes<T>>::mutate(|open| open.retain(|open_id| open_id != &id));
+        <VoteSnapshots<T>>::remove(id);
+        <VoteReleases<T>>::mutate(|queue| queue.push(id));
+    }
+
+    /// Remove a closed proposal and its vote once the shares reserved by its voters are released
+    /// - proposals that are still `ProposalStage::Passed` are kept until they are executed and
+    /// pruned then (see `execute_membership_approvals` and `pay_grants`)
+    fn prune_closed_proposal(id: T::Hash) {
+        if <VoteReleases<T>>::get().contains(&id) {
+            return;
+        }
+        let pruned = match ProposalKind::of::<T>(id) {
+            Some(ProposalKind::Membership) => Self::remove_if_closed::<MembershipProposalOf<T>>(id),
+            Some(ProposalKind::Grant) => Self::remove_if_closed::<GrantProposalOf<T>>(id),
+            Some(ProposalKind::Meta) => Self::remove_if_closed::<MetaProposalOf<T>>(id),
+            None => false,
+        };
+        if pruned {
+            <VoteStates<T>>::remove(id);
+            <Voters<T>>::remove(id);
+            <Crowdfunds<T>>::remove(id);
+            <Contributions<T>>::remove(id);
+        }
+    }
+
+    /// Remove the proposal of kind `P` stored under `id` if it is `ProposalStage::Law` or
+    /// `ProposalStage::Rejected`
+    fn remove_if_closed<P: Proposal<T>>(id: T::Hash) -> bool {
+        match P::get(id).map(|proposal| proposal.stage().clone()) {
+            Some(ProposalStage::Law) | Some(ProposalStage::Rejected) => {
+                P::remove(id);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Set the electorate of every open vote to the bank's `total_shares`
+    /// - called whenever shares are issued or burned so the thresholds in `vote.rs` are
+    /// measured against the shares that currently exist
+    /// - the outcome is re-evaluated on the next vote or when the voting period ends
+    fn update_open_vote_electorates(total_shares: Shares) {
+        for id in <OpenVotes<T>>::get() {
+            <VoteStates<T>>::mutate(id, |state| {
+                if let Some(state) = state {
+                    state.all_voters = total_shares;
+                    state.electorate_weight = Self::electorate_weight(state.weighting);
+                }
+            });
+        }
+    }
+
+    /// Sum of the weights of every member's `total_shares` under `weighting`, without conviction
+    /// (see `VotingState::electorate_weight`)
+    fn electorate_weight(weighting: VoteWeighting) -> Shares {
+        Self::members()
+            .iter()
+            .filter_map(|member| <MembershipShares<T>>::get(member))
+            .fold(0, |weight: Shares, profile| {
+                weight.saturating_add(weighting.weight(profile.total_shares))
+            })
+    }
+
+    /// Release reserved shares for at most `max_releases` voters on closed proposals
+    /// - `VotesByMembers` entries are removed as they are released so that proposals with
+    /// many voters are cleaned up over several blocks instead of one unbounded block
+    /// - votes with conviction are scheduled in `ConvictionUnlocks` instead of being released
+    /// - proposals whose voters are all released are pruned if they are closed
+    fn release_closed_vote_shares(max_releases: u32) {
+        let mut queue = <VoteReleases<T>>::get();
+        if queue.is_empty() {
+            return;
+        }
+        let mut released = Vec::new();
+        let mut remaining = max_releases;
+        while remaining > 0 && !queue.is_empty() {
+            let id = queue[0];
+            let mut voters = <Voters<T>>::get(id);
+            while remaining > 0 {
+                if let Some(voter) = voters.pop() {
+                    if let Some(vote) = <VotesByMembers<T>>::take(id, &voter) {
+                        let conviction = <VoteConvictions<T>>::take(id, &voter);
+                        if conviction == Conviction::None {
+                            Self::release_vote_shares(id, &voter, vote.inner());
+                        } else {
+                            let unlock = <system::Module<T>>::block_number()
+                                + T::ConvictionLockPeriod::get()
+                                    * T::BlockNumber::from(conviction.lock_periods());
+                            <ConvictionUnlocks<T>>::mutate(unlock, |unlocks| {
+                                unlocks.push((id, voter.clone(), vote.inner()))
+                            });
+                        }
                     }
-                    // change total_turnout based on difference
-                },
-                // if a voting option is added, a branch must be added here to account for it
-                Some(_) => return Err(Error::<T>::NewVotingOptionNotHandled.into()),
-            }
-            // get current vote state
-            let current_vote_state = <MembershipVoteStates>::get(index).ok_or(Error::<T>::VoteStateUninitialized)?;
-            let mut new_vote_state = current_vote_state;
-            if new {
-                // no existing votes for this proposal from this member
-                new_reserved += magnitude;
-                // check if the sponsor has enough free shares to afford the sponsor_bond
-                ensure!(
-                    voter_share_profile.total_shares >= new_reserved, Error::<T>::InsufficientMembershipVoteCollateral
-                );
-                if direction {
-                    new_vote_state.in_favor += magnitude;
+                    remaining -= 1;
                 } else {
-                    new_vote_state.against += magnitude;
+                    break;
                 }
-                <VotesByMembers<T>>::insert(index, &voter, vote.clone());
-                let new_share_profile = ShareProfile {
-                    reserved_shares: new_reserved,
-                    total_shares: voter_share_profile.total_shares,
-                };
-                <MembershipShares<T>>::insert(&voter, new_share_profile);
             }
-            if same {
-                // there is an existing vote in the same direction (so aggregate
-                // new_magnitude and old_magnitude in match statement)
-                new_reserved += magnitude;
-                // check if the sponsor has enough free shares to afford the sponsor_bond
-                ensure!(
-                    voter_share_profile.total_shares >= new_reserved,
-                    Error::<T>::InsufficientMembershipVoteCollateral
-                );
-                let new_vote = if direction {
-                    new_vote_state.in_favor += magnitude;
-                    Vote::InFavor(new_magnitude)
-                } else {
-                    new_vote_state.in_favor += magnitude;
-                    Vote::Against(new_magnitude)
-                };
-                <VotesByMembers<T>>::insert(index, &voter, new_vote);
-                let new_share_profile = ShareProfile {
-                    reserved_shares: new_reserved,
-                    total_shares: voter_share_profile.total_shares,
-                };
-                <MembershipShares<T>>::insert(&voter, new_share_profile);
+            if voters.is_empty() {
+                <Voters<T>>::remove(id);
+                queue.remove(0);
+                released.push(id);
+                Self::deposit_event(RawEvent::VoteSharesReleased(id));
+            } else {
+                <Voters<T>>::insert(id, voters);
             }
-            if different {
-                if shares_imbalance_sign {
-                    // positive => increase in_favor by magnitude
-                    new_vote_state.in_favor += magnitude;
-                    new_vote_state.against -= shares_imbalance;
-                } else {
-                    // negative => decrease in_favor by shares_imbalance
-                    new_vote_state.in_favor -= shares_imbalance; // this is wrong!
-                    new_vote_state.against += magnitude;
+        }
+        <VoteReleases<T>>::put(queue);
+        for id in released {
+            Self::prune_closed_proposal(id);
+        }
+    }
+
+    /// Release the shares reserved for votes with conviction whose lock ends at block `n`
+    fn unlock_conviction_votes(n: T::BlockNumber) {
+        for (id, voter, shares) in <ConvictionUnlocks<T>>::take(n) {
+            Self::release_vote_shares(id, &voter, shares);
+            Self::deposit_event(RawEvent::ConvictionVoteUnlocked(id, voter, shares));
+        }
+    }
+
+    /// Change the shares reserved for `voter`'s vote on proposal `id` from `old` to `new`
+    /// - the voter's unreserved shares are used first and the rest is drawn from the members
+    /// that delegated to the voter, in the order they delegated
+    /// - shares drawn for the old vote are returned before drawing for the new one
+    /// - neither the voter's own shares nor a delegator's shares can be used beyond the member's
+    /// `total_shares` at the vote's snapshot
+    /// - nothing is written unless the whole amount can be reserved
+    fn reserve_vote_shares(
+        id: T::Hash,
+        voter: &T::AccountId,
+        old: Shares,
+        new: Shares,
+    ) -> DispatchResult {
+        let mut profile =
+            <MembershipShares<T>>::get(voter).ok_or(Error::<T>::NoMembershipShareInfo)?;
+        let mut lenders: Vec<(T::AccountId, ShareProfile, Delegation<T::AccountId>)> = Vec::new();
+        let mut old_drawn: Shares = 0;
+        for (delegator, shares) in <DelegatedVoteDraws<T>>::get(id, voter) {
+            old_drawn += shares;
+            if let (Some(mut lender), Some(mut delegation)) = (
+                <MembershipShares<T>>::get(&delegator),
+                <Delegations<T>>::get(&delegator),
+            ) {
+                lender.reserved_shares = lender.reserved_shares.saturating_sub(shares);
+                delegation.drawn = delegation.drawn.saturating_sub(shares);
+                lenders.push((delegator, lender, delegation));
+            }
+        }
+        profile.reserved_shares = profile
+            .reserved_shares
+            .saturating_sub(old.saturating_sub(old_drawn));
+
+        let snapshot = <VoteSnapshots<T>>::get(id);
+        let snapshot_shares = |who: &T::AccountId| {
+            snapshot.map_or(Shares::max_value(), |block| Self::shares_at(who, block))
+        };
+        let own = new
+            .min(profile.unreserved_shares())
+            .min(snapshot_shares(voter));
+        profile.reserved_shares += own;
+        let mut outstanding = new - own;
+        let mut draws: Vec<(T::AccountId, Shares)> = Vec::new();
+        for delegator in <DelegatorsOf<T>>::get(voter) {
+            if outstanding.is_zero() {
+                break;
+            }
+            if !lenders.iter().any(|(lender, _, _)| lender == &delegator) {
+                if let (Some(lender), Some(delegation)) = (
+                    <MembershipShares<T>>::get(&delegator),
+                    <Delegations<T>>::get(&delegator),
+                ) {
+                    lenders.push((delegator.clone(), lender, delegation));
                 }
-                if less_shares_reserved {
-                    // less shares are reserved in new vote so subtract how much
-                    // larger the last vote was relative to the new one
-                    // to be deleted, debugging now: </new_vote_state.turnout += difference;>
-                    new_reserved -= difference;
-                } else {
-                    // more shares are reserved in new vote so add how much
-                    // larger the new vote is relative to the last one
-                    //  to be deleted, debugging now: </new_vote_state.turnout += difference;>
-                    new_reserved += difference;
-                    ensure!(
-                        voter_share_profile.total_shares >= new_reserved,
-                        Error::<T>::InsufficientMembershipVoteCollateral
-                    );
+            }
+            if let Some((_, lender, delegation)) = lenders
+                .iter_mut()
+                .find(|(lender, _, _)| lender == &delegator)
+            {
+                let available = delegation
+                    .shares
+                    .saturating_sub(delegation.drawn)
+                    .min(lender.unreserved_shares())
+                    .min(snapshot_shares(&delegator));
+                let drawn = available.min(outstanding);
+                if !drawn.is_zero() {
+                    lender.reserved_shares += drawn;
+                    delegation.drawn += drawn;
+                    outstanding -= drawn;
+                    draws.push((delegator, drawn));
                 }
-                let new_share_profile = ShareProfile {
-                    reserved_shares: new_reserved,
-                    total_shares: voter_share_profile.total_shares,
-                };
-                <MembershipShares<T>>::insert(&voter, new_share_profile);
-                <VotesByMembers<T>>::insert(index, &voter, vote);
             }
+        }
+        ensure!(
+            outstanding.is_zero(),
+            Error::<T>::InsufficientMembershipVoteCollateral
+        );
 
-            if new_vote_state.approved() {
-                // change proposal state to passed and schedule passage in storage via `on_finalize` calls
-                let passed_proposal = MembershipProposal {
-                    stage: ProposalStage::Passed,
-                    ..membership_proposal
-                };
-                // change proposal to passed
-                <MembershipApplications<T>>::insert(index, passed_proposal);
-                // TODO: schedule execution
-                // emit voted and passed events with scheduled execution estimate
+        <MembershipShares<T>>::insert(voter, profile);
+        for (delegator, lender, delegation) in lenders {
+            <MembershipShares<T>>::insert(&delegator, lender);
+            <Delegations<T>>::insert(&delegator, delegation);
+        }
+        if draws.is_empty() {
+            <DelegatedVoteDraws<T>>::remove(id, voter);
+        } else {
+            <DelegatedVoteDraws<T>>::insert(id, voter, draws);
+        }
+        Ok(())
+    }
+
+    /// Release the shares reserved for `voter`'s vote of `magnitude` on proposal `id`,
+    /// returning drawn shares to the delegators they were drawn from
+    fn release_vote_shares(id: T::Hash, voter: &T::AccountId, magnitude: Shares) {
+        let mut drawn: Shares = 0;
+        for (delegator, shares) in <DelegatedVoteDraws<T>>::take(id, voter) {
+            drawn += shares;
+            Self::release_shares(&delegator, shares);
+            <Delegations<T>>::mutate(&delegator, |delegation| {
+                if let Some(delegation) = delegation {
+                    delegation.drawn = delegation.drawn.saturating_sub(shares);
+                }
+            });
+        }
+        Self::release_shares(voter, magnitude.saturating_sub(drawn));
+    }
+
+    /// Remove `delegator`'s delegation to `delegate`
+    fn remove_delegation(delegator: &T::AccountId, delegate: &T::AccountId) {
+        <Delegations<T>>::remove(delegator);
+        <DelegatorsOf<T>>::mutate(delegate, |delegators| delegators.retain(|d| d != delegator));
+    }
+
+    /// Record `who`'s new `total_shares` at the current block
+    /// - checkpoints that neither an open vote's snapshot nor a future one can read are pruned,
+    /// keeping the last one at or before the oldest snapshot that can still be read
+    fn checkpoint_shares(who: &T::AccountId, total_shares: Shares) {
+        let now = <system::Module<T>>::block_number();
+        // votes sponsored from now on snapshot the previous block (see `sponsor`)
+        let oldest_snapshot = <OpenVotes<T>>::get()
+            .into_iter()
+            .filter_map(|id| <VoteSnapshots<T>>::get(id))
+            .fold(now.saturating_sub(One::one()), |oldest, snapshot| {
+                oldest.min(snapshot)
+            });
+        <ShareCheckpoints<T>>::mutate(who, |checkpoints| {
+            match checkpoints.last_mut() {
+                Some((block, shares)) if *block == now => *shares = total_shares,
+                _ => checkpoints.push((now, total_shares)),
+            }
+            let readable = checkpoints
+                .iter()
+                .rposition(|(block, _)| *block <= oldest_snapshot)
+                .unwrap_or(0);
+            checkpoints.drain(..readable);
+        });
+    }
+
+    /// `who`'s `total_shares` at the end of `block` (zero before their first checkpoint)
+    pub fn shares_at(who: &T::AccountId, block: T::BlockNumber) -> Shares {
+        let checkpoints = <ShareCheckpoints<T>>::get(who);
+        match checkpoints.binary_search_by(|(checkpoint, _)| checkpoint.cmp(&block)) {
+            Ok(index) => checkpoints[index].1,
+            Err(0) => 0,
+            Err(index) => checkpoints[index - 1].1,
+        }
+    }
+
+    /// Decrease the shares reserved by `who`
+    fn release_shares(who: &T::AccountId, amount: Shares) {
+        <MembershipShares<T>>::mutate(who, |profile| {
+            if let Some(profile) = profile {
+                profile.reserved_shares = profile.reserved_shares.saturating_sub(amount);
+            }
+        });
+    }
+
+    /// Return the full application bond to the applicant
+    /// - for applications that become `ProposalStage::Law` or are withdrawn before sponsorship
+    /// - crowdfunded applications are refunded to their backers when they are removed, along
+    /// with the applicant's deposit
+    fn refund_application_bond(
+        id: T::Hash,
+        proposal: &MembershipProposal<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+    ) {
+        if let Some(crowdfund) = <Crowdfunds<T>>::take(id) {
+            Self::refund_deposit(id, &proposal.who, crowdfund.deposit);
+            Self::return_contributions(id, false);
+            return;
+        }
+        T::Currency::unreserve(&proposal.who, proposal.bond);
+        Self::deposit_event(RawEvent::MembershipApplicationBondRefunded(
+            id,
+            proposal.who.clone(),
+            proposal.bond,
+        ));
+    }
+
+    /// Slash `ApplicationBondSlash` of the application bond to the bank and return the rest
+    /// - for applications that fail after members spent shares on them
+    fn slash_application_bond(
+        id: T::Hash,
+        proposal: &MembershipProposal<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+    ) {
+        if let Some(crowdfund) = <Crowdfunds<T>>::get(id) {
+            let (to_slash, to_return) = Self::slash_bond(&proposal.who, crowdfund.deposit);
+            Self::deposit_event(RawEvent::MembershipApplicationBondSlashed(
+                id,
+                proposal.who.clone(),
+                to_slash,
+                to_return,
+            ));
+            Self::return_contributions(id, true);
+            return;
+        }
+        let (to_slash, to_return) = Self::slash_bond(&proposal.who, proposal.bond);
+        Self::deposit_event(RawEvent::MembershipApplicationBondSlashed(
+            id,
+            proposal.who.clone(),
+            to_slash,
+            to_return,
+        ));
+    }
+
+    /// Return the deposit reserved by the applicant of a crowdfunded application
+    fn refund_deposit(id: T::Hash, applicant: &T::AccountId, deposit: BalanceOf<T>) {
+        T::Currency::unreserve(applicant, deposit);
+        Self::deposit_event(RawEvent::MembershipApplicationBondRefunded(
+            id,
+            applicant.clone(),
+            deposit,
+        ));
+    }
+
+    /// Return the stake contributed to a failed crowdfunded application and refund, or slash if
+    /// `slash_bonds`, the bond contributed
+    fn return_contributions(id: T::Hash, slash_bonds: bool) {
+        for (backer, bond, stake) in <Contributions<T>>::take(id) {
+            if !stake.is_zero() {
+                T::Currency::unreserve(&backer, stake);
+                Self::deposit_event(RawEvent::MembershipApplicationStakeRefunded(
+                    id,
+                    backer.clone(),
+                    stake,
+                ));
+            }
+            if bond.is_zero() {
+                continue;
+            }
+            if slash_bonds {
+                let (to_slash, to_return) = Self::slash_bond(&backer, bond);
+                Self::deposit_event(RawEvent::MembershipApplicationBondSlashed(
+                    id, backer, to_slash, to_return,
+                ));
             } else {
-                // update vote state
-                <MembershipVoteStates>::insert(index, new_vote_state);
-                // emit voted event (TODO: change this to emit based on branches above to inform client
-                // of changes to storage
+                T::Currency::unreserve(&backer, bond);
+                Self::deposit_event(RawEvent::MembershipApplicationBondRefunded(
+                    id, backer, bond,
+                ));
             }
-            Ok(())
         }
     }
-}
 
-impl<T: Trait> Module<T> {
-    /// Membership checking supporting a single member
-    pub fn is_member(who: &T::AccountId) -> bool {
-        <Members<T>>::get().contains(who)
+    /// Slash `ApplicationBondSlash` of the `bond` reserved by `who` to the bank and return the
+    /// rest (amount slashed, amount returned)
+    fn slash_bond(who: &T::AccountId, bond: BalanceOf<T>) -> (BalanceOf<T>, BalanceOf<T>) {
+        let to_slash = T::ApplicationBondSlash::get() * bond;
+        let (imbalance, _) = T::Currency::slash_reserved(who, to_slash);
+        T::Currency::resolve_creating(&Self::account_id(), imbalance);
+        let to_return = bond.saturating_sub(to_slash);
+        T::Currency::unreserve(who, to_return);
+        (to_slash, to_return)
+    }
+
+    /// Return the full grant application bond to the applicant
+    /// - for grants that pass or expire before sponsorship
+    fn refund_grant_bond(id: T::Hash, grant: &GrantProposalOf<T>) {
+        T::Currency::unreserve(&grant.who, grant.bond);
+        Self::deposit_event(RawEvent::GrantApplicationBondRefunded(
+            id,
+            grant.who.clone(),
+            grant.bond,
+        ));
+    }
+
+    /// Slash `ApplicationBondSlash` of the grant application bond to the bank and return the rest
+    fn slash_grant_bond(id: T::Hash, grant: &GrantProposalOf<T>) {
+        let (to_slash, to_return) = Self::slash_bond(&grant.who, grant.bond);
+        Self::deposit_event(RawEvent::GrantApplicationBondSlashed(
+            id,
+            grant.who.clone(),
+            to_slash,
+            to_return,
+        ));
+    }
+
+    /// Return the meta proposal bond to the proposer
+    fn refund_meta_bond(id: T::Hash, meta_proposal: &MetaProposalOf<T>) {
+        T::Currency::unreserve(&meta_proposal.who, meta_proposal.bond);
+        Self::deposit_event(RawEvent::MetaProposalBondRefunded(
+            id,
+            meta_proposal.who.clone(),
+            meta_proposal.bond,
+        ));
+    }
+
+    /// Slash `ApplicationBondSlash` of the meta proposal bond to the bank and return the rest
+    fn slash_meta_bond(id: T::Hash, meta_proposal: &MetaProposalOf<T>) {
+        let (to_slash, to_return) = Self::slash_bond(&meta_proposal.who, meta_proposal.bond);
+        Self::deposit_event(RawEvent::MetaProposalBondSlashed(
+            id,
+            meta_proposal.who.clone(),
+            to_slash,
+            to_return,
+        ));
+    }
+
+    /// The next payment of a passed grant
+    /// - installments are `amount / payments` and the last one also pays the remainder
+    fn grant_installment(grant: &GrantProposalOf<T>) -> BalanceOf<T> {
+        let payments = grant.schedule.payments();
+        let installment = grant.amount / BalanceOf::<T>::from(payments);
+        if grant.payments_made + 1 < payments {
+            installment
+        } else {
+            grant.amount - installment * BalanceOf::<T>::from(payments - 1)
+        }
+    }
+
+    /// Make the grant payments due at block `n` out of the bank
+    /// - once a payment is made the next installment is scheduled and the last one moves the
+    /// grant to `ProposalStage::Law`
+    /// - payments the bank can't cover (while keeping its account alive) are retried after
+    /// `BatchPeriod`
+    fn pay_grants(n: T::BlockNumber) {
+        for (id, recipient, amount) in <Recipients<T>>::take(n) {
+            let grant = match <GrantApplications<T>>::get(id) {
+                Some(grant) => grant,
+                None => continue,
+            };
+            if T::Currency::transfer(
+                &Self::account_id(),
+                &recipient,
+                amount,
+                ExistenceRequirement::KeepAlive,
+            )
+            .is_err()
+            {
+                <Recipients<T>>::mutate(n + Self::batch_period(), |due| {
+                    due.push((id, recipient.clone(), amount))
+                });
+                Self::deposit_event(RawEvent::GrantPaymentDeferred(id, recipient, amount));
+                continue;
+            }
+            // shares are untouched so the spend only lowers the capital backing them
+            let bank = <BankAccount<T>>::get();
+            let capital = Self::bank_balance(bank.clone()).unwrap_or_else(|_| Zero::zero());
+            Self::deposit_event(RawEvent::GrantPaid(
+                id,
+                recipient.clone(),
+                amount,
+                bank.shares,
+                capital,
+            ));
+            let mut grant = GrantProposal {
+                payments_made: grant.payments_made + 1,
+                ..grant
+            };
+            match grant.schedule {
+                GrantSchedule::Installments { period, .. }
+                    if grant.payments_made < grant.schedule.payments() =>
+                {
+                    let installment = Self::grant_installment(&grant);
+                    <Recipients<T>>::mutate(n + period, |due| {
+                        due.push((id, recipient, installment))
+                    });
+                }
+                _ => grant.stage = ProposalStage::Law,
+            }
+            <GrantApplications<T>>::insert(id, grant);
+            Self::prune_closed_proposal(id);
+        }
+    }
+
+    /// Absorb approved membership proposals into the organization
+    /// - called every `BatchPeriod` in `on_finalize`
+    /// - total issuance in a batch is capped at `MaximumShareIssuance` of the shares that
+    /// existed at the start of the batch
+    /// - approvals are executed in strict FIFO order; the first one that doesn't fit under the
+    /// remaining cap and every one after it keep their place in `MembershipApprovals` for the
+    /// next batch
+    /// - an approval larger than the whole cap executes alone at the head of a batch so that it
+    /// can't hold up the queue forever
+    fn execute_membership_approvals() {
+        let approvals = <MembershipApprovals<T>>::take();
+        if approvals.is_empty() {
+            return;
+        }
+        let mut bank = <BankAccount<T>>::get();
+        let issuance_cap: Shares = Self::maximum_share_issuance() * bank.shares;
+        let mut issued: Shares = 0;
+        let mut deferred: Vec<T::Hash> = Vec::new();
+        for id in approvals {
+            if let Some(proposal) = <MembershipApplications<T>>::get(id) {
+                let issued_after = issued.saturating_add(proposal.shares_requested);
+                // once one approval is deferred every approval after it is too
+                if !deferred.is_empty() || (issued_after > issuance_cap && !issued.is_zero()) {
+                    Self::deposit_event(RawEvent::MembershipApplicationDeferred(
+                        id,
+                        proposal.shares_requested,
+                    ));
+                    deferred.push(id);
+                    continue;
+                }
+                if Self::execute_membership_proposal(id, proposal.clone(), &mut bank).is_ok() {
+                    issued = issued_after;
+                } else {
+                    // the applicant reneged on the stake promised so the bond is slashed and
+                    // the proposal is closed so that the applicant can apply again
+                    Self::slash_application_bond(id, &proposal);
+                    Self::deposit_event(RawEvent::MembershipApplicationExecutionFailed(
+                        id,
+                        proposal.who.clone(),
+                    ));
+                    let failed_proposal = MembershipProposal {
+                        stage: ProposalStage::Rejected,
+                        ..proposal
+                    };
+                    <MembershipApplications<T>>::insert(id, failed_proposal);
+                }
+                Self::prune_closed_proposal(id);
+            }
+        }
+        <MembershipApprovals<T>>::put(deferred);
+        if !issued.is_zero() {
+            Self::update_open_vote_electorates(bank.shares);
+        }
+        <BankAccount<T>>::put(bank);
+    }
+
+    /// Execute a single passed membership proposal
+    /// - transfers `stake_promised` from the applicant to the bank account
+    /// - issues `shares_requested` and grants them to the applicant
+    fn execute_membership_proposal(
+        id: T::Hash,
+        proposal: MembershipProposal<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+        bank: &mut Bank<T::AccountId>,
+    ) -> DispatchResult {
+        ensure!(
+            proposal.stage == ProposalStage::Passed,
+            Error::<T>::RequestInWrongStage
+        );
+        if let Some(crowdfund) = <Crowdfunds<T>>::get(id) {
+            Self::execute_crowdfund(id, &proposal, crowdfund, bank);
+        } else {
+            T::Currency::transfer(
+                &proposal.who,
+                &Self::account_id(),
+                proposal.stake_promised,
+                ExistenceRequirement::KeepAlive,
+            )?;
+            Self::grant_shares(&proposal.who, proposal.shares_requested, bank);
+            Self::refund_application_bond(id, &proposal);
+        }
+        Self::deposit_event(RawEvent::MembershipApplicationExecuted(
+            id,
+            proposal.who.clone(),
+            proposal.stake_promised,
+            proposal.shares_requested,
+        ));
+        let executed_proposal = MembershipProposal {
+            stage: ProposalStage::Law,
+            ..proposal
+        };
+        <MembershipApplications<T>>::insert(id, executed_proposal);
+        Ok(())
+    }
+
+    /// Move the stake contributed to a crowdfunded application into the bank, refund the bond
+    /// contributed and the applicant's deposit and split the shares requested between the
+    /// applicant and the backers
+    /// - backers split `backer_share` of the shares in proportion to the bond and stake they
+    /// contributed and the applicant receives the rest, including any rounding remainder
+    /// - a backer whose reserved stake was slashed since they contributed is only credited with
+    /// the stake that reached the bank, and the shares they forfeit aren't issued
+    fn execute_crowdfund(
+        id: T::Hash,
+        proposal: &MembershipProposal<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+        crowdfund: CrowdfundOf<T>,
+        bank: &mut Bank<T::AccountId>,
+    ) {
+        let raised = crowdfund.bond_raised.saturating_add(crowdfund.stake_raised);
+        let backers_shares = crowdfund.backer_share * proposal.shares_requested;
+        let mut unassigned = backers_shares;
+        Self::refund_deposit(id, &proposal.who, crowdfund.deposit);
+        for (backer, bond, stake) in <Contributions<T>>::take(id) {
+            // the bank account exists, so only stake that is no longer reserved stays behind
+            let remainder = T::Currency::repatriate_reserved(&backer, &Self::account_id(), stake)
+                .unwrap_or(stake);
+            T::Currency::unreserve(&backer, bond);
+            Self::deposit_event(RawEvent::MembershipApplicationBondRefunded(
+                id,
+                backer.clone(),
+                bond,
+            ));
+            let contributed = bond.saturating_add(stake);
+            let entitled = (Permill::from_rational_approximation(contributed, raised)
+                * backers_shares)
+                .min(unassigned);
+            unassigned -= entitled;
+            let shares = Permill::from_rational_approximation(
+                contributed.saturating_sub(remainder),
+                contributed,
+            ) * entitled;
+            if !shares.is_zero() {
+                Self::grant_shares(&backer, shares, bank);
+                Self::deposit_event(RawEvent::MembershipSharesToBacker(id, backer, shares));
+            }
+        }
+        let applicants_shares = proposal.shares_requested - backers_shares + unassigned;
+        if !applicants_shares.is_zero() {
+            Self::grant_shares(&proposal.who, applicants_shares, bank);
+        }
+    }
+
+    /// Issue `shares` to `who`, who joins the organization if they aren't a member yet
+    fn grant_shares(who: &T::AccountId, shares: Shares, bank: &mut Bank<T::AccountId>) {
+        bank.issue(shares);
+        let share_profile = <MembershipShares<T>>::get(who)
+            .map(|profile| ShareProfile {
+                total_shares: profile.total_shares + shares,
+                ..profile
+            })
+            .unwrap_or(ShareProfile {
+                reserved_shares: 0u32,
+                total_shares: shares,
+            });
+        Self::checkpoint_shares(who, share_profile.total_shares);
+        <MembershipShares<T>>::insert(who, share_profile);
+        if !Self::is_member(who) {
+            <Members<T>>::mutate(|members| members.push(who.clone()));
+        }
     }
 
     // -- MAKE BELOW METHODS SPECIFIC TO SOME TRAIT
@@ -563,4 +2234,78 @@ impl<T: Trait> Module<T> {
         // this still returns `T::AccountId`
         BANK_ID.into_account()
     }
+
+    /// Return the amount in the bank (in T::Currency denomination)
+    pub fn bank_balance(bank: Bank<T::AccountId>) -> Result<BalanceOf<T>, Error<T>> {
+        let account = bank.joint_account.inner().ok_or(Error::<T>::NoBankOwner)?;
+        let balance = T::Currency::free_balance(&account)
+            // TODO: ponder whether this should be here (not if I don't follow the same existential
+            // deposit system as polkadot...)
+            // Must never be less than 0 but better be safe.
+            .saturating_sub(T::Currency::minimum_balance());
+        Ok(balance)
+    }
+
+    /// Calculate the shares to capital ratio
+    /// TODO: is this type conversion safe?
+    /// ...I just want to use `Permill::from_rational_approximation` which requires inputs two of
+    /// the same type
+    pub fn shares_to_capital_ratio(shares: Shares, capital: BalanceOf<T>) -> Permill {
+        let shares_as_balance = BalanceOf::<T>::from(shares);
+        Permill::from_rational_approximation(shares_as_balance, capital)
+    }
+
+    /// Ratio of the `bank.balance` to `bank.shares`
+    /// - this value may be interpreted as `currency_per_share` by UIs, but that would assume
+    /// immediate liquidity which is false
+    pub fn collateralization_ratio(bank: Bank<T::AccountId>) -> Result<Permill, Error<T>> {
+        let most_recent_balance = Self::bank_balance(bank.clone())?;
+        Ok(Self::shares_to_capital_ratio(
+            bank.shares,
+            most_recent_balance,
+        ))
+    }
+
+    /// Capital paid out by the bank in exchange for burning `shares`
+    /// - proportional to the bank's collateralization, i.e. `shares / bank.shares` of `bank_balance`
+    /// - computed directly rather than by inverting `collateralization_ratio` because that
+    /// `Permill` saturates whenever there are more shares than capital
+    pub fn capital_for_shares(
+        shares: Shares,
+        bank: Bank<T::AccountId>,
+    ) -> Result<BalanceOf<T>, Error<T>> {
+        ensure!(!bank.shares.is_zero(), Error::<T>::NoMembershipShareInfo);
+        let most_recent_balance = Self::bank_balance(bank.clone())?;
+        Ok(
+            most_recent_balance.saturating_mul(BalanceOf::<T>::from(shares))
+                / BalanceOf::<T>::from(bank.shares),
+        )
+    }
+}
+
+impl<T: Trait> ActionBond for Module<T> {
+    type Shares = Shares;
+    type Capital = BalanceOf<T>;
+
+    fn conversion_comparison(shares: Shares, capital: BalanceOf<T>) -> ConversionRate {
+        ConversionRate::from_shares_and_capital(BalanceOf::<T>::from(shares), capital)
+    }
+}
+
+impl<T: Trait> CalculateCollateralReq for Module<T> {
+    type Shares = Shares;
+    type Capital = BalanceOf<T>;
+
+    /// Multiply the base bond by `T::BondCurve` at the new ratio's shares per capital
+    /// relative to the bank's
+    /// - less dilutive ratios improve share value if accepted so they need a smaller bond than
+    /// more dilutive ones, with no cliff at the bank's ratio to game
+    fn calculate_collateral_req(
+        bond: BondType<BalanceOf<T>, Shares>,
+        new_parity: ConversionRate,
+        existing_parity: ConversionRate,
+    ) -> BondType<BalanceOf<T>, Shares> {
+        let relative = relative_shares_per_capital(&new_parity, &existing_parity);
+        bond.saturating_scale(T::BondCurve::multiplier(relative))
+    }
 }
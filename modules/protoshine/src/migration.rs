@@ -0,0 +1,216 @@
+//! Storage migrations, run in `on_initialize` for chains with an older `StorageVersion`
+//! - the module's hooks and dispatchables wait until storage is in the `CURRENT_VERSION`
+//! - `Version::V1` => `Version::V2`: `u32` proposal indices to `T::Hash` proposal ids
+//! - `Version::V2` => `Version::V3`: share checkpoints and vote snapshots
+//! - `Version::V3` => `Version::V4`: economic parameters in storage
+use super::*;
+
+use frame_support::storage::migration::{put_storage_value, take_storage_value};
+use frame_support::{Blake2_256, StorageHasher, Twox64Concat};
+use sp_runtime::traits::One;
+
+const MODULE: &[u8] = b"Protoshine";
+
+/// Storage layout of chains built from the current genesis
+pub(crate) const CURRENT_VERSION: Version = Version::V4;
+
+/// `MembershipProposal` layout while proposals were keyed by `u32`
+/// - every application reserved the same `MembershipProposalBond` so it wasn't stored
+#[derive(Encode, Decode)]
+struct LegacyMembershipProposal<AccountId, BalanceOf, BlockNumber> {
+    index: u32,
+    who: AccountId,
+    stake_promised: BalanceOf,
+    shares_requested: Shares,
+    stage: ProposalStage,
+    time_proposed: BlockNumber,
+}
+
+/// `VotingState` layout before votes carried a conviction and weighting
+/// - only the threshold is kept because the tally is rebuilt from `VotesByMembers`
+#[derive(Encode, Decode)]
+struct LegacyVotingState {
+    in_favor: Shares,
+    against: Shares,
+    all_voters: Shares,
+    threshold: VoteThreshold,
+}
+
+/// Hashed suffix of the `u32` key in maps declared without an explicit hasher
+fn legacy_key(index: u32) -> Vec<u8> {
+    index.using_encoded(Blake2_256::hash).to_vec()
+}
+
+/// Progress of `migrate_to_hashed_proposals`, which migrates a chunk of legacy proposals in each
+/// block (see `HashedIdMigration`)
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug)]
+pub struct HashedIdProgress<Hash> {
+    /// Next legacy index to migrate
+    next: u32,
+    /// Last legacy index, i.e. `MembershipApplicationCount` when the migration started
+    last: u32,
+    /// The legacy `MembershipApprovals` in order, with the id of each once it is migrated
+    approvals: Vec<(u32, Option<Hash>)>,
+}
+
+/// Move the proposals made under the `u32` index scheme to their hash ids, at most
+/// `MaximumVoteReleases` indices per block, and return whether every index was migrated
+/// - indices are bounded by `MembershipApplicationCount` when the migration starts, which is
+/// kept as is; the legacy `MembershipApprovals` are set aside until the last chunk so that
+/// batches in the meantime can't overwrite them
+/// - `VotesByMembers` can't be iterated so voters are recovered by looking up the vote of every
+/// account in `Members` (only members could vote); the sponsor's vote wasn't marked so the
+/// first of them in `Members` order is treated as the sponsor (see `retract`)
+/// - expiries are rescheduled from the migrated proposals (open votes get a fresh
+/// `VotingPeriod` because the sponsorship block was never stored)
+/// - closed proposals are queued in `VoteReleases` so their voters' shares are released and
+/// they are pruned like any other closed proposal
+/// - open votes are tracked in `OpenVotes` with the bank's shares as their electorate and a
+/// tally rebuilt from their migrated votes
+pub(crate) fn migrate_to_hashed_proposals<T: Trait>() -> bool {
+    let now = <system::Module<T>>::block_number();
+    let mut progress = <HashedIdMigration<T>>::get().unwrap_or_else(|| {
+        let approvals: Vec<u32> =
+            take_storage_value(MODULE, b"MembershipApprovals", &[]).unwrap_or_default();
+        HashedIdProgress {
+            next: 1,
+            last: <Module<T>>::membership_application_count(),
+            approvals: approvals.into_iter().map(|index| (index, None)).collect(),
+        }
+    });
+    let end = progress
+        .next
+        .saturating_add(T::MaximumVoteReleases::get().max(1));
+    while progress.next < end && progress.next <= progress.last {
+        if let Some((index, id)) = migrate_proposal::<T>(progress.next, now) {
+            for (approved, migrated) in progress.approvals.iter_mut() {
+                if *approved == index {
+                    *migrated = Some(id);
+                }
+            }
+        }
+        progress.next += 1;
+    }
+    if progress.next <= progress.last {
+        <HashedIdMigration<T>>::put(progress);
+        return false;
+    }
+
+    <HashedIdMigration<T>>::kill();
+    // legacy approvals passed before any made since the migration started
+    let mut approvals: Vec<T::Hash> = progress
+        .approvals
+        .into_iter()
+        .filter_map(|(_, id)| id)
+        .collect();
+    approvals.extend(<MembershipApprovals<T>>::get());
+    <MembershipApprovals<T>>::put(approvals);
+    true
+}
+
+/// Move the proposal stored under the legacy key `index` to its hash id, with its vote
+/// - returns the proposal's legacy index and new id, if there was a proposal to move
+fn migrate_proposal<T: Trait>(index: u32, now: T::BlockNumber) -> Option<(u32, T::Hash)> {
+    let legacy: Option<LegacyMembershipProposal<T::AccountId, BalanceOf<T>, T::BlockNumber>> =
+        take_storage_value(MODULE, b"MembershipApplications", &legacy_key(index));
+    let legacy = legacy?;
+    let nonce = <Module<T>>::membership_application_nonce(&legacy.who);
+    let id = <Module<T>>::membership_proposal_id(
+        &legacy.who,
+        legacy.stake_promised,
+        legacy.shares_requested,
+        nonce,
+    );
+    <MembershipApplicationNonces<T>>::insert(&legacy.who, nonce + 1);
+
+    match legacy.stage {
+        ProposalStage::Application => {
+            let expiry = legacy.time_proposed + T::ApplicationTimeLimit::get();
+            let expiry = if expiry > now {
+                expiry
+            } else {
+                now + One::one()
+            };
+            <ProposalExpiries<T>>::mutate(expiry, |v| v.push(id));
+        }
+        ProposalStage::Voting => {
+            <VoteExpiries<T>>::mutate(now + T::VotingPeriod::get(), |v| v.push(id));
+            <OpenVotes<T>>::mutate(|open| open.push(id));
+        }
+        // closed votes still hold their voters' reserved shares
+        _ => <VoteReleases<T>>::mutate(|queue| queue.push(id)),
+    }
+    let proposal = MembershipProposal {
+        who: legacy.who,
+        stake_promised: legacy.stake_promised,
+        shares_requested: legacy.shares_requested,
+        bond: T::MembershipProposalBond::get(),
+        stage: legacy.stage,
+        time_proposed: legacy.time_proposed,
+    };
+    <MembershipApplications<T>>::insert(id, proposal);
+
+    let mut votes: Vec<Vote> = Vec::new();
+    let mut voters: Vec<T::AccountId> = Vec::new();
+    for member in <Module<T>>::members() {
+        let mut key = legacy_key(index);
+        key.extend(member.using_encoded(Twox64Concat::hash));
+        if let Some(vote) = take_storage_value::<Vote>(MODULE, b"VotesByMembers", &key) {
+            <VotesByMembers<T>>::insert(id, &member, vote.clone());
+            votes.push(vote);
+            voters.push(member);
+        }
+    }
+    if !voters.is_empty() {
+        <Voters<T>>::insert(id, voters);
+    }
+
+    if let Some(legacy_state) =
+        take_storage_value::<LegacyVotingState>(MODULE, b"MembershipVoteStates", &legacy_key(index))
+    {
+        // legacy votes were opened with a placeholder electorate of 1 and their tallies
+        // drifted from the votes, so the tally is rebuilt from the migrated votes (which had
+        // no conviction) to keep later vote changes and retractions consistent with it
+        let empty = VotingState {
+            in_favor: 0,
+            against: 0,
+            turnout: 0,
+            all_voters: <BankAccount<T>>::get().shares,
+            electorate_weight: <BankAccount<T>>::get().shares,
+            threshold: legacy_state.threshold,
+            weighting: VoteWeighting::Linear,
+        };
+        let vote_state = votes.into_iter().fold(empty, |state, vote| {
+            match state.apply(None, (vote, Conviction::None)) {
+                Ok((tallied, _)) => tallied,
+                Err(_) => state,
+            }
+        });
+        <VoteStates<T>>::insert(id, vote_state);
+    }
+    Some((legacy.index, id))
+}
+
+/// Start the share checkpoint history of every member with their current `total_shares`
+/// - open votes get the current block as their snapshot because earlier holdings weren't recorded
+pub(crate) fn migrate_to_share_checkpoints<T: Trait>() {
+    let now = <system::Module<T>>::block_number();
+    for member in <Module<T>>::members() {
+        if let Some(profile) = <Module<T>>::membership_shares(&member) {
+            <ShareCheckpoints<T>>::insert(&member, vec![(now, profile.total_shares)]);
+        }
+    }
+    for id in <Module<T>>::open_votes() {
+        <VoteSnapshots<T>>::insert(id, now);
+    }
+}
+
+/// Initialize the parameters that meta proposals can change with the `Trait` constants
+pub(crate) fn migrate_to_parameter_storage<T: Trait>() {
+    <MembershipProposalBond<T>>::put(T::MembershipProposalBond::get());
+    MembershipSponsorBond::put(T::MembershipSponsorBond::get());
+    MembershipVoteBond::put(T::MembershipVoteBond::get());
+    MaximumShareIssuance::put(T::MaximumShareIssuance::get());
+    MembershipConsensusThreshold::put(T::MembershipConsensusThreshold::get());
+    <BatchPeriod<T>>::put(T::BatchPeriod::get());
+}
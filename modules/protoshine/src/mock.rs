@@ -2,7 +2,9 @@
 // - todo: _generate_ more
 use super::*;
 
-use frame_support::{assert_noop, assert_ok, impl_outer_origin, parameter_types, weights::Weight};
+use frame_support::{
+    assert_noop, assert_ok, impl_outer_event, impl_outer_origin, parameter_types, weights::Weight,
+};
 use sp_core::H256;
 use sp_runtime::{
     testing::Header,
@@ -14,6 +16,17 @@ impl_outer_origin! {
     pub enum Origin for Test where system = frame_system {}
 }
 
+mod protoshine {
+    pub use crate::Event;
+}
+
+impl_outer_event! {
+    pub enum TestEvent for Test where system = frame_system {
+        pallet_balances<T>,
+        protoshine<T>,
+    }
+}
+
 #[derive(Clone, Eq, PartialEq)]
 pub struct Test;
 parameter_types! {
@@ -32,7 +45,7 @@ impl frame_system::Trait for Test {
     type AccountId = u64;
     type Lookup = IdentityLookup<Self::AccountId>;
     type Header = Header;
-    type Event = ();
+    type Event = TestEvent;
     type BlockHashCount = BlockHashCount;
     type MaximumBlockWeight = MaximumBlockWeight;
     type AvailableBlockRatio = AvailableBlockRatio;
@@ -50,7 +63,7 @@ impl pallet_balances::Trait for Test {
     type OnNewAccount = ();
     type OnFreeBalanceZero = ();
     type OnReapAccount = System;
-    type Event = ();
+    type Event = TestEvent;
     type TransferPayment = ();
     type DustRemoval = ();
     type ExistentialDeposit = ExistentialDeposit;
@@ -58,70 +71,51 @@ impl pallet_balances::Trait for Test {
     type CreationFee = CreationFee;
 }
 parameter_types! {
-    pub const MembershipProposalBond: u64 = 2;
+    pub const MembershipProposalBond: u64 = 1;
     pub const MembershipSponsorBond: u32 = 3;
     pub const MembershipVoteBond: u32 = 1;
     pub const MaximumShareIssuance: Permill = Permill::from_percent(50);
     pub const MembershipConsensusThreshold: Permill = Permill::from_percent(67);
+    pub const MembershipQuorum: Permill = Permill::from_percent(10);
     pub const BatchPeriod: u64 = 2;
+    pub const MaximumVoteReleases: u32 = 2;
+    pub const ApplicationBondSlash: Permill = Permill::from_percent(50);
+    pub const ApplicationTimeLimit: u64 = 5;
+    pub const VotingPeriod: u64 = 4;
+    pub const ConvictionLockPeriod: u64 = 3;
+    pub const GrantProposalBond: u64 = 2;
+    pub const GrantSponsorBond: u32 = 3;
+    pub const GrantConsensusThreshold: Permill = Permill::from_percent(67);
+    pub const GrantQuorum: Permill = Permill::from_percent(20);
+    pub const MetaProposalBond: u64 = 2;
+    pub const MetaConsensusThreshold: Permill = Permill::from_percent(75);
+    pub const MetaQuorum: Permill = Permill::from_percent(50);
 }
 impl Trait for Test {
     type Currency = pallet_balances::Module<Test>;
-    // not testing event emission in this runtime or using it?
-    type Event = ();
+    type Event = TestEvent;
     type MembershipProposalBond = MembershipProposalBond;
+    type AdmissionPolicy = ();
     type MembershipSponsorBond = MembershipSponsorBond;
     type MembershipVoteBond = MembershipVoteBond;
     type MaximumShareIssuance = MaximumShareIssuance;
     type MembershipConsensusThreshold = MembershipConsensusThreshold;
+    type MembershipQuorum = MembershipQuorum;
     type BatchPeriod = BatchPeriod;
+    type MaximumVoteReleases = MaximumVoteReleases;
+    type BondCurve = collateral::PiecewiseLinear;
+    type ApplicationBondSlash = ApplicationBondSlash;
+    type ApplicationTimeLimit = ApplicationTimeLimit;
+    type VotingPeriod = VotingPeriod;
+    type ConvictionLockPeriod = ConvictionLockPeriod;
+    type GrantProposalBond = GrantProposalBond;
+    type GrantSponsorBond = GrantSponsorBond;
+    type GrantConsensusThreshold = GrantConsensusThreshold;
+    type GrantQuorum = GrantQuorum;
+    type MetaProposalBond = MetaProposalBond;
+    type MetaConsensusThreshold = MetaConsensusThreshold;
+    type MetaQuorum = MetaQuorum;
 }
 pub type System = frame_system::Module<Test>;
 pub type Balances = pallet_balances::Module<Test>;
 pub type Protoshine = Module<Test>;
-
-// useful for getting the bank_balance when you need to calculate the bank's collateralization ratio
-// - see ../collateral
-impl<AccountId> Owner<AccountId> {
-    pub(crate) fn inner(self) -> Option<AccountId> {
-        if let Owner::Owned(account) = self {
-            Some(account)
-        } else {
-            None
-        }
-    }
-}
-
-use sp_runtime::traits::Saturating;
-impl<T: Trait> Module<T> {
-    /// Return the amount in the bank (in T::Currency denomination)
-    pub fn bank_balance(bank: Bank<T::AccountId>) -> Result<BalanceOf<T>, Error<T>> {
-        let account = bank.joint_account.inner().ok_or(Error::<T>::NoBankOwner)?;
-        let balance = T::Currency::free_balance(&account)
-            // TODO: ponder whether this should be here (not if I don't follow the same existential
-            // deposit system as polkadot...)
-            // Must never be less than 0 but better be safe.
-            .saturating_sub(T::Currency::minimum_balance());
-        Ok(balance)
-    }
-
-    /// Calculate the shares to capital ratio
-    /// TODO: is this type conversion safe?
-    /// ...I just want to use `Permill::from_rational_approximation` which requires inputs two of
-    /// the same type
-    pub fn shares_to_capital_ratio(shares: Shares, capital: BalanceOf<T>) -> Permill {
-        let shares_as_balance = BalanceOf::<T>::from(shares);
-        Permill::from_rational_approximation(shares_as_balance, capital)
-    }
-
-    /// Ratio of the `bank.balance` to `bank.shares`
-    /// - this value may be interpreted as `currency_per_share` by UIs, but that would assume
-    /// immediate liquidity which is false
-    pub fn collateralization_ratio(bank: Bank<T::AccountId>) -> Result<Permill, Error<T>> {
-        let most_recent_balance = Self::bank_balance(bank.clone())?;
-        Ok(Self::shares_to_capital_ratio(
-            bank.shares,
-            most_recent_balance,
-        ))
-    }
-}
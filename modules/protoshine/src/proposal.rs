@@ -0,0 +1,311 @@
+//! Proposal kinds
+//! - every kind shares the sponsorship and voting logic in `lib.rs` (`sponsor_proposal`,
+//! `vote_on_proposal` and `retract_vote`) and only defines what differs through `Proposal`
+
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[non_exhaustive]
+/// The kinds of proposal that members sponsor and vote on
+pub enum ProposalKind {
+    /// Applications to join the organization (`MembershipProposal`)
+    Membership,
+    /// Grants paid out of the bank (`GrantProposal`)
+    Grant,
+    /// Changes to the economic parameters (`MetaProposal`)
+    Meta,
+}
+
+impl ProposalKind {
+    /// The kind of the proposal stored under `id`, if any
+    /// - proposal ids are hashes of each kind's terms prefixed with a tag of the kind (e.g.
+    /// `b"membership"`) so they don't collide across kinds
+    pub fn of<T: Trait>(id: T::Hash) -> Option<ProposalKind> {
+        if <MembershipApplications<T>>::exists(id) {
+            Some(ProposalKind::Membership)
+        } else if <GrantApplications<T>>::exists(id) {
+            Some(ProposalKind::Grant)
+        } else if <MetaProposals<T>>::exists(id) {
+            Some(ProposalKind::Meta)
+        } else {
+            None
+        }
+    }
+}
+
+/// Stages, bonds, threshold and execution of one kind of proposal
+/// - the vote on every kind is a `VotingState` stored in `VoteStates`
+pub trait Proposal<T: Trait>: Sized {
+    /// The proposal stored under `id`
+    fn get(id: T::Hash) -> Option<Self>;
+    /// Store the proposal under `id`
+    fn insert(self, id: T::Hash);
+    /// Remove the proposal stored under `id`
+    fn remove(id: T::Hash);
+    /// The current stage of the proposal
+    fn stage(&self) -> &ProposalStage;
+    /// The proposal moved to `stage`
+    fn with_stage(self, stage: ProposalStage) -> Self;
+    /// Ok if the proposal can be sponsored, on top of being in `ProposalStage::Application`
+    fn ensure_sponsorable(&self, _id: T::Hash) -> Result<(), Error<T>> {
+        Ok(())
+    }
+    /// Shares reserved by the member that sponsors the proposal
+    fn sponsor_bond(&self) -> Shares;
+    /// Minimum magnitude of each vote on the proposal
+    fn vote_bond(&self) -> Shares;
+    /// Threshold the vote is opened with
+    fn threshold(&self) -> VoteThreshold;
+    /// Called when the proposal wasn't sponsored within `ApplicationTimeLimit`
+    /// - removes the proposal
+    fn on_expired(self, id: T::Hash);
+    /// Called once the proposal is sponsored and open to voting
+    fn on_sponsored(&self, id: T::Hash, sponsor_bond: Shares);
+    /// Execution hook, called once the `VotingPeriod` ends with the vote meeting its threshold
+    /// - stores the proposal in its next stage
+    fn on_passed(self, id: T::Hash, vote_state: &VotingState);
+    /// Called once the `VotingPeriod` ends with the vote short of its threshold
+    /// - stores the proposal in its next stage
+    fn on_rejected(self, id: T::Hash, vote_state: &VotingState);
+}
+
+impl<T: Trait> Proposal<T> for MembershipProposal<T::AccountId, BalanceOf<T>, T::BlockNumber> {
+    fn get(id: T::Hash) -> Option<Self> {
+        <MembershipApplications<T>>::get(id)
+    }
+    fn insert(self, id: T::Hash) {
+        <MembershipApplications<T>>::insert(id, self);
+    }
+    fn remove(id: T::Hash) {
+        <MembershipApplications<T>>::remove(id);
+    }
+    fn stage(&self) -> &ProposalStage {
+        &self.stage
+    }
+    fn with_stage(self, stage: ProposalStage) -> Self {
+        MembershipProposal { stage, ..self }
+    }
+    /// Crowdfunded applications must raise their bond and stake first (see `Crowdfunds`)
+    fn ensure_sponsorable(&self, id: T::Hash) -> Result<(), Error<T>> {
+        let funded = <Crowdfunds<T>>::get(id).map_or(true, |crowdfund| {
+            crowdfund.is_funded(&self.bond, &self.stake_promised)
+        });
+        ensure!(funded, Error::<T>::ApplicationNotFullyFunded);
+        Ok(())
+    }
+    fn sponsor_bond(&self) -> Shares {
+        <Module<T>>::membership_sponsor_bond()
+    }
+    fn vote_bond(&self) -> Shares {
+        <Module<T>>::membership_vote_bond()
+    }
+    fn threshold(&self) -> VoteThreshold {
+        VoteThreshold::TurnoutBiased {
+            threshold: <Module<T>>::membership_consensus_threshold(),
+            quorum: T::MembershipQuorum::get(),
+        }
+    }
+    fn on_expired(self, id: T::Hash) {
+        <MembershipApplications<T>>::remove(id);
+        <Module<T>>::refund_application_bond(id, &self);
+        <Module<T>>::deposit_event(RawEvent::MembershipApplicationExpired(id));
+    }
+    fn on_sponsored(&self, id: T::Hash, sponsor_bond: Shares) {
+        <Module<T>>::deposit_event(RawEvent::MembershipApplicationSponsored(
+            id,
+            sponsor_bond,
+            self.stake_promised,
+            self.shares_requested,
+        ));
+    }
+    /// Passed applications are absorbed in the next batch (see `execute_membership_approvals`)
+    fn on_passed(self, id: T::Hash, vote_state: &VotingState) {
+        let passed_proposal = MembershipProposal {
+            stage: ProposalStage::Passed,
+            ..self
+        };
+        <MembershipApplications<T>>::insert(id, passed_proposal);
+        <MembershipApprovals<T>>::mutate(|v| v.push(id));
+        <Module<T>>::deposit_event(RawEvent::MembershipApplicationPassed(
+            id,
+            vote_state.in_favor,
+            vote_state.against,
+        ));
+    }
+    /// The application bond is slashed because members spent shares on the application
+    fn on_rejected(self, id: T::Hash, vote_state: &VotingState) {
+        <Module<T>>::slash_application_bond(id, &self);
+        let rejected_proposal = MembershipProposal {
+            stage: ProposalStage::Rejected,
+            ..self
+        };
+        <MembershipApplications<T>>::insert(id, rejected_proposal);
+        <Module<T>>::deposit_event(RawEvent::MembershipApplicationRejected(
+            id,
+            vote_state.in_favor,
+            vote_state.against,
+        ));
+    }
+}
+
+impl<T: Trait> Proposal<T> for GrantProposal<T::AccountId, BalanceOf<T>, T::BlockNumber> {
+    fn get(id: T::Hash) -> Option<Self> {
+        <GrantApplications<T>>::get(id)
+    }
+    fn insert(self, id: T::Hash) {
+        <GrantApplications<T>>::insert(id, self);
+    }
+    fn remove(id: T::Hash) {
+        <GrantApplications<T>>::remove(id);
+    }
+    fn stage(&self) -> &ProposalStage {
+        &self.stage
+    }
+    fn with_stage(self, stage: ProposalStage) -> Self {
+        GrantProposal { stage, ..self }
+    }
+    fn sponsor_bond(&self) -> Shares {
+        T::GrantSponsorBond::get()
+    }
+    fn vote_bond(&self) -> Shares {
+        <Module<T>>::membership_vote_bond()
+    }
+    fn threshold(&self) -> VoteThreshold {
+        VoteThreshold::TurnoutBiased {
+            threshold: T::GrantConsensusThreshold::get(),
+            quorum: T::GrantQuorum::get(),
+        }
+    }
+    fn on_expired(self, id: T::Hash) {
+        <GrantApplications<T>>::remove(id);
+        <Module<T>>::refund_grant_bond(id, &self);
+        <Module<T>>::deposit_event(RawEvent::GrantApplicationExpired(id));
+    }
+    fn on_sponsored(&self, id: T::Hash, sponsor_bond: Shares) {
+        <Module<T>>::deposit_event(RawEvent::GrantApplicationSponsored(
+            id,
+            sponsor_bond,
+            self.amount,
+        ));
+    }
+    /// The first payment is due at the end of this block (see `Module::pay_grants`)
+    fn on_passed(self, id: T::Hash, vote_state: &VotingState) {
+        <Module<T>>::refund_grant_bond(id, &self);
+        let now = <system::Module<T>>::block_number();
+        let installment = <Module<T>>::grant_installment(&self);
+        <Recipients<T>>::mutate(now, |due| {
+            due.push((id, self.recipient.clone(), installment))
+        });
+        let passed_grant = GrantProposal {
+            stage: ProposalStage::Passed,
+            ..self
+        };
+        <GrantApplications<T>>::insert(id, passed_grant);
+        <Module<T>>::deposit_event(RawEvent::GrantApplicationPassed(
+            id,
+            vote_state.in_favor,
+            vote_state.against,
+        ));
+    }
+    fn on_rejected(self, id: T::Hash, vote_state: &VotingState) {
+        <Module<T>>::slash_grant_bond(id, &self);
+        let rejected_grant = GrantProposal {
+            stage: ProposalStage::Rejected,
+            ..self
+        };
+        <GrantApplications<T>>::insert(id, rejected_grant);
+        <Module<T>>::deposit_event(RawEvent::GrantApplicationRejected(
+            id,
+            vote_state.in_favor,
+            vote_state.against,
+        ));
+    }
+}
+
+impl<T: Trait> Proposal<T> for MetaProposal<T::AccountId, BalanceOf<T>, T::BlockNumber> {
+    fn get(id: T::Hash) -> Option<Self> {
+        <MetaProposals<T>>::get(id)
+    }
+    fn insert(self, id: T::Hash) {
+        <MetaProposals<T>>::insert(id, self);
+    }
+    fn remove(id: T::Hash) {
+        <MetaProposals<T>>::remove(id);
+    }
+    fn stage(&self) -> &ProposalStage {
+        &self.stage
+    }
+    fn with_stage(self, stage: ProposalStage) -> Self {
+        MetaProposal { stage, ..self }
+    }
+    fn sponsor_bond(&self) -> Shares {
+        <Module<T>>::membership_sponsor_bond()
+    }
+    fn vote_bond(&self) -> Shares {
+        <Module<T>>::membership_vote_bond()
+    }
+    fn threshold(&self) -> VoteThreshold {
+        VoteThreshold::QuorumConsensus {
+            threshold: T::MetaConsensusThreshold::get(),
+            quorum: T::MetaQuorum::get(),
+        }
+    }
+    fn on_expired(self, id: T::Hash) {
+        <MetaProposals<T>>::remove(id);
+        <Module<T>>::refund_meta_bond(id, &self);
+        <Module<T>>::deposit_event(RawEvent::MetaProposalExpired(id));
+    }
+    fn on_sponsored(&self, id: T::Hash, sponsor_bond: Shares) {
+        <Module<T>>::deposit_event(RawEvent::MetaProposalSponsored(id, sponsor_bond));
+    }
+    /// The change takes effect right away
+    /// - votes that are already open keep the threshold they were opened with
+    /// - the change is checked again because shares may have changed hands since it was
+    /// proposed; if it is no longer valid the proposal is rejected and its bond refunded
+    fn on_passed(self, id: T::Hash, vote_state: &VotingState) {
+        <Module<T>>::refund_meta_bond(id, &self);
+        if <Module<T>>::ensure_valid_parameter_change(&self.change).is_err() {
+            let invalidated_proposal = MetaProposal {
+                stage: ProposalStage::Rejected,
+                ..self
+            };
+            <MetaProposals<T>>::insert(id, invalidated_proposal);
+            <Module<T>>::deposit_event(RawEvent::MetaProposalInvalidated(
+                id,
+                vote_state.in_favor,
+                vote_state.against,
+            ));
+            return;
+        }
+        <Module<T>>::change_parameter(self.change.clone());
+        let executed_proposal = MetaProposal {
+            stage: ProposalStage::Law,
+            ..self
+        };
+        <MetaProposals<T>>::insert(id, executed_proposal);
+        <Module<T>>::deposit_event(RawEvent::ParameterChanged(
+            id,
+            <system::Module<T>>::block_number(),
+            vote_state.in_favor,
+            vote_state.against,
+        ));
+    }
+    fn on_rejected(self, id: T::Hash, vote_state: &VotingState) {
+        <Module<T>>::slash_meta_bond(id, &self);
+        let rejected_proposal = MetaProposal {
+            stage: ProposalStage::Rejected,
+            ..self
+        };
+        <MetaProposals<T>>::insert(id, rejected_proposal);
+        <Module<T>>::deposit_event(RawEvent::MetaProposalRejected(
+            id,
+            vote_state.in_favor,
+            vote_state.against,
+        ));
+    }
+}
@@ -2,7 +2,9 @@
 use super::*;
 use mock::*;
 
-use frame_support::{assert_err, assert_noop, assert_ok};
+use frame_support::{assert_err, assert_noop, assert_ok, parameter_types, traits::Contains};
+use sp_core::H256;
+use sp_runtime::traits::{OnFinalize, OnInitialize};
 
 fn new_test_ext() -> sp_io::TestExternalities {
     let mut t = frame_system::GenesisConfig::default()
@@ -99,7 +101,7 @@ fn membership_check_works() {
 fn membership_application_enforces_panics() {
     new_test_ext().execute_with(|| {
         let seven = Origin::signed(7);
-        // no freebies for membership applications, adding `enforced-criteria` is an upcoming feature
+        // no freebies for membership applications, further criteria are the `AdmissionPolicy`
         assert_err!(
             Protoshine::membership_application(seven.clone(), 0, 5),
             Error::<Test>::InvalidMembershipApplication
@@ -117,10 +119,11 @@ fn membership_application_enforces_panics() {
 
 #[test]
 fn poor_cant_afford_membership_application() {
-    // I name this test intentionally because *crowdfunding* applications that can't afford bonds is coming soon :)
+    // I name this test intentionally because applicants that can't afford bonds can *crowdfund* them
+    // (see `crowdfunded_applications_split_shares_with_backers`)
     new_test_ext().execute_with(|| {
         let seven = Origin::signed(7);
-        // no freebies for membership applications, adding `enforced-criteria` is an upcoming feature
+        // no freebies for membership applications, further criteria are the `AdmissionPolicy`
         assert_err!(
             Protoshine::membership_application(seven.clone(), 2, 10),
             Error::<Test>::InsufficientMembershipApplicantCollateral
@@ -128,27 +131,124 @@ fn poor_cant_afford_membership_application() {
     });
 }
 
+parameter_types! {
+    pub const MinimumStakePromised: u64 = 5;
+    pub const AtBanksRatio: u128 = collateral::ONE;
+    pub const ShareRequestCap: Permill = Permill::from_percent(10);
+}
+
+/// Accounts 8 and 9
+struct Listed;
+impl Contains<u64> for Listed {
+    fn sorted_members() -> Vec<u64> {
+        vec![8, 9]
+    }
+}
+
+/// Check an application against `P` as if it were the mock's `AdmissionPolicy`
+fn admit<P: AdmissionPolicy<Test>>(
+    who: u64,
+    stake_promised: u64,
+    shares: Shares,
+) -> DispatchResult {
+    P::admit(&who, stake_promised, shares).map_err(Into::into)
+}
+
+#[test]
+fn admission_policies_reject_with_the_violated_criterion() {
+    use admission::*;
+    new_test_ext().execute_with(|| {
+        type Minimum = MinimumStake<MinimumStakePromised>;
+        assert_err!(admit::<Minimum>(8, 4, 4), Error::<Test>::StakeBelowMinimum);
+        assert_ok!(admit::<Minimum>(8, 5, 5));
+
+        // the bank is at parity
+        type NotDilutive = MaximumDilution<AtBanksRatio>;
+        assert_ok!(admit::<NotDilutive>(8, 10, 10));
+        assert_ok!(admit::<NotDilutive>(8, 11, 10));
+        assert_err!(
+            admit::<NotDilutive>(8, 5, 10),
+            Error::<Test>::MembershipApplicationIsRidiculous
+        );
+
+        // 10% of the 60 existing shares
+        type Capped = MaximumShareRequest<ShareRequestCap>;
+        assert_ok!(admit::<Capped>(8, 6, 6));
+        assert_err!(
+            admit::<Capped>(8, 7, 7),
+            Error::<Test>::TooManySharesRequested
+        );
+
+        assert_ok!(admit::<AllowList<Listed>>(8, 10, 10));
+        assert_err!(
+            admit::<AllowList<Listed>>(10, 10, 10),
+            Error::<Test>::ApplicantNotAllowed
+        );
+        assert_err!(
+            admit::<DenyList<Listed>>(8, 10, 10),
+            Error::<Test>::ApplicantDenied
+        );
+        assert_ok!(admit::<DenyList<Listed>>(10, 10, 10));
+
+        // the first violated criterion of a tuple is returned
+        type Both = (Minimum, AllowList<Listed>);
+        assert_err!(admit::<Both>(10, 4, 4), Error::<Test>::StakeBelowMinimum);
+        assert_err!(admit::<Both>(10, 5, 5), Error::<Test>::ApplicantNotAllowed);
+        assert_ok!(admit::<Both>(8, 5, 5));
+    });
+}
+
+#[test]
+fn one_open_application_per_account() {
+    new_test_ext().execute_with(|| {
+        type OneOpen = admission::OneOpenApplication;
+        assert_ok!(admit::<OneOpen>(8, 10, 10));
+        let id = apply(8, 10, 10);
+        assert_eq!(Protoshine::latest_membership_application(&8), Some(id));
+        assert_err!(
+            admit::<OneOpen>(8, 5, 5),
+            Error::<Test>::ApplicantHasOpenApplication
+        );
+        assert_ok!(admit::<OneOpen>(9, 5, 5));
+
+        assert_ok!(Protoshine::withdraw_membership_application(
+            Origin::signed(8),
+            id
+        ));
+        assert_ok!(admit::<OneOpen>(8, 5, 5));
+    });
+}
+
 #[test]
 fn membership_application_works() {
     new_test_ext().execute_with(|| {
         System::set_block_number(8);
         let eight = Origin::signed(8);
+        // the id can be computed before submitting
+        let id = Protoshine::membership_proposal_id(&8, 10, 10, 0);
         assert_ok!(Protoshine::membership_application(eight.clone(), 10, 10));
 
         // check the storage item
         assert_eq!(Protoshine::membership_application_count(), 1);
+        assert_eq!(Protoshine::membership_application_nonce(&8), 1);
         let expected_membership_app = MembershipProposal {
-            index: 1,
             who: 8,
             stake_promised: 10,
             shares_requested: 10,
+            bond: 2,
             stage: ProposalStage::Application,
             time_proposed: 8,
         };
         assert_eq!(
-            Protoshine::membership_applications(1).unwrap(),
+            Protoshine::membership_applications(id).unwrap(),
             expected_membership_app
         );
+
+        // the same terms get a new id because the nonce increased
+        assert_ok!(Protoshine::membership_application(eight, 10, 10));
+        let second_id = Protoshine::membership_proposal_id(&8, 10, 10, 1);
+        assert_ne!(id, second_id);
+        assert!(Protoshine::membership_applications(second_id).is_some());
     });
 }
 
@@ -166,23 +266,2040 @@ fn application_bond_works() {
         let _ = Protoshine::membership_application(eight.clone(), 10, 10);
 
         // ratio == collateral_ratio
-        // => bond = 2 * MembershipProposalBond constant = 2 * 1 = 2
+        // => bond = 2 * MembershipProposalBond constant = 2 * 1 = 2 (see `PiecewiseLinear`)
         // 616 - 2 = 614
         assert_eq!(Balances::free_balance(&8), 614);
 
         let _ = Protoshine::membership_application(eight.clone(), 11, 10);
 
-        // ratio < collateral_ratio
-        // => bond =  MembershipProposalBond constant
+        // ratio < collateral_ratio, 10/11 of the bank's shares per capital
+        // => bond = 1.909 * MembershipProposalBond constant, rounded down to 1
         // 614 - 1 = 613
-        // assert_eq!(Balances::free_balance(&8), 613);
+        assert_eq!(Balances::free_balance(&8), 613);
+
+        let _ = Protoshine::membership_application(eight, 5, 10);
+
+        // ratio > banks_collateral_ratio, twice the bank's shares per capital
+        // => bond = 4 * MembershipProposalBond constant = 4 * 1 = 4
+        // 613 - 4 = 609
+        assert_eq!(Balances::free_balance(&8), 609);
+        assert_eq!(Balances::reserved_balance(&8), 7);
+    });
+}
+
+#[test]
+fn an_empty_bank_compares_applications_with_parity() {
+    use admission::*;
+    new_test_ext().execute_with(|| {
+        <BankAccount<Test>>::mutate(|bank| bank.shares = 0);
+        assert_eq!(
+            Protoshine::banks_conversion_rate().unwrap(),
+            ConversionRate::parity()
+        );
+        // bonds are scaled as if the bank were at parity instead of saturating
+        assert_eq!(Protoshine::membership_application_bond(10, 10).unwrap(), 2);
+        assert_eq!(Protoshine::membership_application_bond(11, 10).unwrap(), 1);
+        assert_eq!(Protoshine::membership_application_bond(5, 10).unwrap(), 4);
+        // and applications at parity are no more dilutive than the bank
+        type NotDilutive = MaximumDilution<AtBanksRatio>;
+        assert_ok!(admit::<NotDilutive>(8, 10, 10));
+        assert_err!(
+            admit::<NotDilutive>(8, 5, 10),
+            Error::<Test>::MembershipApplicationIsRidiculous
+        );
+    });
+}
+
+#[test]
+fn conversion_rates_are_ordered_by_capital_per_share() {
+    let rate = |shares, capital| Protoshine::conversion_comparison(shares, capital);
+    assert_eq!(rate(10, 5).to_rational(), (5, 10));
+    assert_eq!(rate(5, 10).to_rational(), (10, 5));
+    assert_eq!(rate(10, 5), rate(2, 1));
+    assert_eq!(rate(10, 10), ConversionRate::parity());
+    assert_eq!(rate(0, 0), ConversionRate::parity());
+    // fewer shares per capital is greater, on either side of parity
+    assert!(rate(10, 5) < rate(10, 8));
+    assert!(rate(10, 8) < ConversionRate::parity());
+    assert!(ConversionRate::parity() < rate(8, 10));
+    assert!(rate(8, 10) < rate(5, 10));
+    // exact, where a `Permill` would round both to 333_333
+    assert!(rate(3_000_001, 1_000_000) < rate(3, 1));
+    // large amounts are compared without overflowing
+    let max = u128::max_value();
+    assert!(
+        ConversionRate::from_shares_and_capital(max, max - 1)
+            > ConversionRate::from_shares_and_capital(max - 1, max - 2)
+    );
+    assert!(!rate(10, 5).same_sign(&ConversionRate::parity()));
+    assert!(rate(10, 5).same_sign(&rate(3, 1)));
+    assert!(rate(10, 5).is_capital_over_share());
+    assert_eq!(ConversionRate::parity().inner(), None);
+    assert_eq!(rate(5, 10).inner(), Some(Permill::from_percent(50)));
+}
+
+#[test]
+fn bond_curves_are_continuous() {
+    use collateral::{Exponential, Linear, PiecewiseLinear, ONE};
+    let at = [0, ONE / 2, ONE, 2 * ONE, 3 * ONE];
+    let multipliers = |curve: fn(u128) -> u128| at.iter().map(|r| curve(*r)).collect::<Vec<_>>();
+    assert_eq!(
+        multipliers(Linear::multiplier),
+        vec![ONE, 3 * ONE / 2, 2 * ONE, 3 * ONE, 4 * ONE]
+    );
+    assert_eq!(
+        multipliers(Exponential::multiplier),
+        vec![ONE, 1_414_125, 2 * ONE, 4 * ONE, 8 * ONE]
+    );
+    assert_eq!(
+        multipliers(PiecewiseLinear::multiplier),
+        vec![ONE, 3 * ONE / 2, 2 * ONE, 4 * ONE, 4 * ONE]
+    );
+    // no cliff at the bank's ratio
+    assert_eq!(PiecewiseLinear::multiplier(ONE - 1), 2 * ONE - 1);
+    assert_eq!(PiecewiseLinear::multiplier(ONE + 1), 2 * ONE + 2);
+    assert_eq!(Exponential::multiplier(ONE - 1), 2 * ONE - 2);
+    assert_eq!(Exponential::multiplier(200 * ONE), u128::max_value());
+
+    // 8 capital for 10 shares against a bank at parity
+    assert_eq!(
+        collateral::relative_shares_per_capital(
+            &Protoshine::conversion_comparison(10, 8),
+            &ConversionRate::parity()
+        ),
+        5 * ONE / 4
+    );
+    // amounts that would overflow the products are rounded, not saturated
+    assert_eq!(
+        collateral::relative_shares_per_capital(
+            &ConversionRate::from_shares_and_capital(5u128 << 100, 4u128 << 100),
+            &ConversionRate::parity()
+        ),
+        5 * ONE / 4
+    );
+    // 5 capital for 10 shares against a bank with 2 capital per share
+    assert_eq!(
+        collateral::relative_shares_per_capital(
+            &Protoshine::conversion_comparison(10, 5),
+            &Protoshine::conversion_comparison(10, 20)
+        ),
+        4 * ONE
+    );
+}
+
+/// Apply for membership and return the id of the new application
+fn apply(who: u64, stake_promised: u64, shares_requested: Shares) -> H256 {
+    let nonce = Protoshine::membership_application_nonce(&who);
+    assert_ok!(Protoshine::membership_application(
+        Origin::signed(who),
+        stake_promised,
+        shares_requested
+    ));
+    Protoshine::membership_proposal_id(&who, stake_promised, shares_requested, nonce)
+}
+
+/// Events deposited by the module so far
+fn protoshine_events() -> Vec<Event<Test>> {
+    System::events()
+        .into_iter()
+        .filter_map(|record| match record.event {
+            TestEvent::protoshine(event) => Some(event),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Sponsor (by member 1), vote (by members 2 to 4) and wait out the `VotingPeriod` so that the
+/// proposal of kind `P` with `id` passes
+fn sponsor_and_pass<P: Proposal<Test>>(id: H256) {
+    assert_ok!(Protoshine::sponsor_proposal(
+        Origin::signed(1),
+        id,
+        VoteWeighting::Linear
+    ));
+    for voter in 2..5 {
+        assert_ok!(Protoshine::vote_on_proposal(
+            Origin::signed(voter),
+            id,
+            Vote::InFavor(3),
+            Conviction::None
+        ));
+    }
+    <Protoshine as OnInitialize<u64>>::on_initialize(
+        System::block_number() + <Test as Trait>::VotingPeriod::get(),
+    );
+    assert_eq!(
+        P::get(id).map(|proposal| proposal.stage().clone()),
+        Some(ProposalStage::Passed)
+    );
+}
+
+#[test]
+fn shared_dispatchables_find_the_kind_of_each_proposal() {
+    new_test_ext().execute_with(|| {
+        let id = apply(8, 10, 10);
+        assert_eq!(ProposalKind::of::<Test>(id), Some(ProposalKind::Membership));
+        let unknown = H256::repeat_byte(7);
+        assert_eq!(ProposalKind::of::<Test>(unknown), None);
+        assert_noop!(
+            Protoshine::sponsor_proposal(Origin::signed(1), unknown, VoteWeighting::Linear),
+            Error::<Test>::IndexWithNoAssociatedProposal
+        );
+        assert_noop!(
+            Protoshine::vote_on_proposal(
+                Origin::signed(1),
+                unknown,
+                Vote::InFavor(1),
+                Conviction::None
+            ),
+            Error::<Test>::IndexWithNoAssociatedProposal
+        );
+        assert_noop!(
+            Protoshine::retract_vote(Origin::signed(1), unknown),
+            Error::<Test>::IndexWithNoAssociatedProposal
+        );
+    });
+}
+
+#[test]
+fn passed_membership_applications_execute_in_batches() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let id = apply(8, 10, 10);
+        sponsor_and_pass::<MembershipProposalOf<Test>>(id);
+        assert_eq!(Protoshine::membership_approvals(), vec![id]);
+
+        // BatchPeriod is 2 so nothing happens in block 1
+        <Protoshine as OnFinalize<u64>>::on_finalize(1);
+        assert!(!Protoshine::is_member(&8));
+
+        <Protoshine as OnFinalize<u64>>::on_finalize(2);
+        assert!(Protoshine::is_member(&8));
+        assert_eq!(
+            Protoshine::membership_shares(&8).unwrap(),
+            ShareProfile {
+                reserved_shares: 0,
+                total_shares: 10,
+            }
+        );
+        // the votes were released so the executed proposal is pruned
+        assert!(Protoshine::vote_releases().is_empty());
+        assert!(Protoshine::membership_applications(id).is_none());
+        assert!(Protoshine::vote_states(id).is_none());
+        assert!(Protoshine::membership_approvals().is_empty());
+        let bank = Protoshine::bank_account();
+        assert_eq!(bank.shares, 70);
+        assert_eq!(Protoshine::bank_balance(bank).unwrap(), 70);
+        // 616 - 10 (stake) because the bond was refunded on execution
+        assert_eq!(Balances::free_balance(&8), 606);
+        assert_eq!(Balances::reserved_balance(&8), 0);
+    });
+}
+
+#[test]
+fn batch_issuance_is_capped_and_excess_is_deferred() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        // 50% of the 60 existing shares => 30 shares may be issued in the first batch
+        let first = apply(8, 20, 20);
+        let second = apply(9, 10, 15);
+        let third = apply(10, 5, 5);
+        for id in &[first, second, third] {
+            sponsor_and_pass::<MembershipProposalOf<Test>>(*id);
+        }
+        assert_eq!(
+            Protoshine::membership_approvals(),
+            vec![first, second, third]
+        );
+
+        <Protoshine as OnFinalize<u64>>::on_finalize(2);
+        // 20 + 15 > 30 so the second application waits and the third waits behind it
+        let deferrals: Vec<_> = protoshine_events()
+            .into_iter()
+            .filter(|event| match event {
+                RawEvent::MembershipApplicationDeferred(..) => true,
+                _ => false,
+            })
+            .collect();
+        assert_eq!(
+            deferrals,
+            vec![
+                RawEvent::MembershipApplicationDeferred(second, 15),
+                RawEvent::MembershipApplicationDeferred(third, 5),
+            ]
+        );
+        assert!(Protoshine::is_member(&8));
+        assert!(!Protoshine::is_member(&9));
+        assert!(!Protoshine::is_member(&10));
+        assert_eq!(Protoshine::membership_approvals(), vec![second, third]);
+        assert_eq!(Protoshine::bank_account().shares, 80);
+        assert_eq!(
+            Protoshine::membership_applications(second).unwrap().stage,
+            ProposalStage::Passed
+        );
+
+        // 50% of 80 => 40 shares may be issued in the next batch
+        <Protoshine as OnFinalize<u64>>::on_finalize(4);
+        assert!(Protoshine::is_member(&9));
+        assert!(Protoshine::is_member(&10));
+        assert!(Protoshine::membership_approvals().is_empty());
+        assert_eq!(Protoshine::bank_account().shares, 100);
+    });
+}
+
+#[test]
+fn approvals_larger_than_the_cap_execute_alone_at_the_head_of_a_batch() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let oversized = apply(8, 40, 40);
+        let next = apply(9, 5, 5);
+        for id in &[oversized, next] {
+            sponsor_and_pass::<MembershipProposalOf<Test>>(*id);
+        }
+
+        // 40 > 30 but nothing else was issued in the batch
+        <Protoshine as OnFinalize<u64>>::on_finalize(2);
+        assert!(Protoshine::is_member(&8));
+        assert!(!Protoshine::is_member(&9));
+        assert_eq!(Protoshine::membership_approvals(), vec![next]);
+        assert_eq!(Protoshine::bank_account().shares, 100);
+
+        <Protoshine as OnFinalize<u64>>::on_finalize(4);
+        assert!(Protoshine::is_member(&9));
+        assert!(Protoshine::membership_approvals().is_empty());
+        assert_eq!(Protoshine::bank_account().shares, 105);
+    });
+}
+
+#[test]
+fn leave_membership_pays_out_proportional_capital() {
+    new_test_ext().execute_with(|| {
+        assert_err!(
+            Protoshine::leave_membership(Origin::signed(8), 5),
+            Error::<Test>::NotAMember
+        );
+        assert_err!(
+            Protoshine::leave_membership(Origin::signed(3), 0),
+            Error::<Test>::InvalidShareBurn
+        );
+        assert_err!(
+            Protoshine::leave_membership(Origin::signed(3), 11),
+            Error::<Test>::InsufficientUnreservedShares
+        );
+
+        // 5 of 60 shares => 5 of 60 capital
+        assert_ok!(Protoshine::leave_membership(Origin::signed(3), 5));
+        assert_eq!(Balances::free_balance(&3), 44);
+        assert_eq!(Protoshine::bank_account().shares, 55);
+        assert_eq!(
+            Protoshine::bank_balance(Protoshine::bank_account()).unwrap(),
+            55
+        );
+        assert_eq!(
+            Protoshine::membership_shares(&3).unwrap(),
+            ShareProfile {
+                reserved_shares: 0,
+                total_shares: 5,
+            }
+        );
+        assert!(Protoshine::is_member(&3));
+
+        // burning the rest removes the member
+        assert_ok!(Protoshine::leave_membership(Origin::signed(3), 5));
+        assert_eq!(Balances::free_balance(&3), 49);
+        assert!(!Protoshine::is_member(&3));
+        assert!(Protoshine::membership_shares(&3).is_none());
+    });
+}
+
+#[test]
+fn reserved_shares_block_leave_membership() {
+    new_test_ext().execute_with(|| {
+        let id = apply(8, 10, 10);
+        // sponsoring reserves 3 of member 1's 10 shares
+        assert_ok!(Protoshine::sponsor_proposal(
+            Origin::signed(1),
+            id,
+            VoteWeighting::Linear
+        ));
+        assert_err!(
+            Protoshine::leave_membership(Origin::signed(1), 10),
+            Error::<Test>::InsufficientUnreservedShares
+        );
+        assert_ok!(Protoshine::leave_membership(Origin::signed(1), 7));
+        assert!(Protoshine::is_member(&1));
+    });
+}
+
+#[test]
+fn reserved_shares_are_released_in_chunks_when_vote_closes() {
+    new_test_ext().execute_with(|| {
+        let id = apply(8, 10, 10);
+        assert_ok!(Protoshine::sponsor_proposal(
+            Origin::signed(1),
+            id,
+            VoteWeighting::Linear
+        ));
+        assert_ok!(Protoshine::vote_on_proposal(
+            Origin::signed(2),
+            id,
+            Vote::Against(5),
+            Conviction::None
+        ));
+        // 18 shares meet the quorum of 6 and 13 of them in favor exceed 67% of the quorum
+        assert_ok!(Protoshine::vote_on_proposal(
+            Origin::signed(3),
+            id,
+            Vote::InFavor(10),
+            Conviction::None
+        ));
+        <Protoshine as OnInitialize<u64>>::on_initialize(4);
+        assert_eq!(
+            Protoshine::membership_applications(id).unwrap().stage,
+            ProposalStage::Passed
+        );
+        assert_eq!(Protoshine::voters(id), vec![1, 2, 3]);
+        assert_eq!(Protoshine::vote_releases(), vec![id]);
+        assert_eq!(
+            Protoshine::membership_shares(&1).unwrap().reserved_shares,
+            3
+        );
+
+        // MaximumVoteReleases is 2 so the last two voters are released first
+        <Protoshine as OnFinalize<u64>>::on_finalize(1);
+        assert_eq!(
+            Protoshine::membership_shares(&3).unwrap().reserved_shares,
+            0
+        );
+        assert_eq!(
+            Protoshine::membership_shares(&2).unwrap().reserved_shares,
+            0
+        );
+        assert_eq!(
+            Protoshine::membership_shares(&1).unwrap().reserved_shares,
+            3
+        );
+        assert!(Protoshine::votes_by_members(id, &2).is_none());
+        assert_eq!(Protoshine::voters(id), vec![1]);
+
+        <Protoshine as OnFinalize<u64>>::on_finalize(3);
+        assert_eq!(
+            Protoshine::membership_shares(&1).unwrap().reserved_shares,
+            0
+        );
+        assert!(Protoshine::votes_by_members(id, &1).is_none());
+        assert!(Protoshine::voters(id).is_empty());
+        assert!(Protoshine::vote_releases().is_empty());
+        // the passed proposal is kept until it is executed
+        assert_eq!(
+            Protoshine::membership_applications(id).unwrap().stage,
+            ProposalStage::Passed
+        );
+    });
+}
+
+#[test]
+fn application_bond_is_slashed_if_stake_is_not_paid() {
+    new_test_ext().execute_with(|| {
+        // 9 can't pay 16 after reserving the bond of 2 out of 17
+        let id = apply(9, 16, 16);
+        assert_eq!(Balances::reserved_balance(&9), 2);
+        sponsor_and_pass::<MembershipProposalOf<Test>>(id);
+
+        <Protoshine as OnFinalize<u64>>::on_finalize(2);
+        assert!(!Protoshine::is_member(&9));
+        // 50% of the bond is slashed to the bank and the rest is returned
+        assert_eq!(Balances::reserved_balance(&9), 0);
+        assert_eq!(Balances::free_balance(&9), 16);
+        assert_eq!(
+            Protoshine::bank_balance(Protoshine::bank_account()).unwrap(),
+            61
+        );
+        assert_eq!(Protoshine::bank_account().shares, 60);
+        // the failed application is closed so the applicant may apply again
+        assert_eq!(
+            Protoshine::membership_applications(id).unwrap().stage,
+            ProposalStage::Rejected
+        );
+        assert!(Protoshine::membership_approvals().is_empty());
+        assert_ok!(admit::<admission::OneOpenApplication>(9, 5, 5));
+    });
+}
+
+/// Open a crowdfunded application and return its id
+fn crowdfund(
+    who: u64,
+    stake_promised: u64,
+    shares_requested: Shares,
+    backer_share: Permill,
+) -> H256 {
+    let nonce = Protoshine::membership_application_nonce(&who);
+    assert_ok!(Protoshine::crowdfunded_membership_application(
+        Origin::signed(who),
+        stake_promised,
+        shares_requested,
+        backer_share
+    ));
+    Protoshine::membership_proposal_id(&who, stake_promised, shares_requested, nonce)
+}
+
+#[test]
+fn crowdfunded_applications_split_shares_with_backers() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        // 10 can't afford the bond of 2 and the stake, only the deposit of 1
+        let id = crowdfund(10, 10, 10, Permill::from_percent(40));
+        assert_eq!(Balances::reserved_balance(&10), 1);
+        assert_err!(
+            Protoshine::sponsor_proposal(Origin::signed(1), id, VoteWeighting::Linear),
+            Error::<Test>::ApplicationNotFullyFunded
+        );
+
+        assert_ok!(Protoshine::back_membership_application(
+            Origin::signed(8),
+            id,
+            2,
+            4
+        ));
+        assert_err!(
+            Protoshine::back_membership_application(Origin::signed(9), id, 0, 7),
+            Error::<Test>::ContributionExceedsRemaining
+        );
+        assert_err!(
+            Protoshine::back_membership_application(Origin::signed(7), id, 1, 0),
+            Error::<Test>::ContributionExceedsRemaining
+        );
+        assert_ok!(Protoshine::back_membership_application(
+            Origin::signed(9),
+            id,
+            0,
+            6
+        ));
+        assert_eq!(
+            Protoshine::crowdfunds(id).unwrap(),
+            Crowdfund {
+                backer_share: Permill::from_percent(40),
+                deposit: 1,
+                bond_raised: 2,
+                stake_raised: 10,
+            }
+        );
+        assert_eq!(Protoshine::contributions(id), vec![(8, 2, 4), (9, 0, 6)]);
+        assert_eq!(Balances::reserved_balance(&8), 6);
+        assert_eq!(Balances::reserved_balance(&9), 6);
+
+        sponsor_and_pass::<MembershipProposalOf<Test>>(id);
+        <Protoshine as OnFinalize<u64>>::on_finalize(2);
+        assert_eq!(
+            Protoshine::membership_applications(id).unwrap().stage,
+            ProposalStage::Law
+        );
+        // 40% of 10 shares split evenly because 8 and 9 each contributed 6 of the 12 raised
+        assert_eq!(Protoshine::membership_shares(&10).unwrap().total_shares, 6);
+        assert_eq!(Protoshine::membership_shares(&8).unwrap().total_shares, 2);
+        assert_eq!(Protoshine::membership_shares(&9).unwrap().total_shares, 2);
+        for who in &[8, 9, 10] {
+            assert!(Protoshine::is_member(who));
+        }
+        let bank = Protoshine::bank_account();
+        assert_eq!(bank.shares, 70);
+        assert_eq!(Protoshine::bank_balance(bank).unwrap(), 70);
+        // the stake moved to the bank and the bond and deposit were refunded
+        assert_eq!(Balances::free_balance(&10), 10);
+        assert_eq!(Balances::free_balance(&8), 612);
+        assert_eq!(Balances::free_balance(&9), 11);
+        assert_eq!(Balances::reserved_balance(&8), 0);
+        assert_eq!(Balances::reserved_balance(&9), 0);
+        assert!(Protoshine::contributions(id).is_empty());
+    });
+}
+
+#[test]
+fn expired_crowdfunds_refund_their_backers() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_err!(
+            Protoshine::crowdfunded_membership_application(
+                Origin::signed(7),
+                10,
+                10,
+                Permill::from_percent(40)
+            ),
+            Error::<Test>::InsufficientMembershipApplicantCollateral
+        );
+        let id = crowdfund(10, 10, 10, Permill::from_percent(40));
+        assert_ok!(Protoshine::back_membership_application(
+            Origin::signed(8),
+            id,
+            2,
+            4
+        ));
+        assert_err!(
+            Protoshine::back_membership_application(Origin::signed(9), id, 0, 0),
+            Error::<Test>::EmptyContribution
+        );
+        assert_err!(
+            Protoshine::back_membership_application(Origin::signed(8), apply(9, 5, 5), 0, 1),
+            Error::<Test>::NotACrowdfundedApplication
+        );
+
+        <Protoshine as OnInitialize<u64>>::on_initialize(6);
+        assert!(Protoshine::membership_applications(id).is_none());
+        assert!(Protoshine::crowdfunds(id).is_none());
+        assert!(Protoshine::contributions(id).is_empty());
+        assert_eq!(Balances::reserved_balance(&8), 0);
+        assert_eq!(Balances::free_balance(&8), 616);
+        assert_eq!(Balances::reserved_balance(&10), 0);
+        assert_eq!(Balances::free_balance(&10), 10);
+    });
+}
+
+#[test]
+fn slashed_backers_forfeit_their_shares() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let id = crowdfund(10, 10, 10, Permill::from_percent(40));
+        assert_ok!(Protoshine::back_membership_application(
+            Origin::signed(8),
+            id,
+            2,
+            4
+        ));
+        assert_ok!(Protoshine::back_membership_application(
+            Origin::signed(9),
+            id,
+            0,
+            6
+        ));
+        sponsor_and_pass::<MembershipProposalOf<Test>>(id);
+        // half of the stake reserved by 9 is slashed before the application is executed
+        let _ = Balances::slash_reserved(&9, 3);
+
+        <Protoshine as OnFinalize<u64>>::on_finalize(2);
+        assert_eq!(Protoshine::membership_shares(&10).unwrap().total_shares, 6);
+        assert_eq!(Protoshine::membership_shares(&8).unwrap().total_shares, 2);
+        // 9 forfeits half of their 2 shares, which aren't issued
+        assert_eq!(Protoshine::membership_shares(&9).unwrap().total_shares, 1);
+        let bank = Protoshine::bank_account();
+        assert_eq!(bank.shares, 69);
+        assert_eq!(Protoshine::bank_balance(bank).unwrap(), 67);
+        assert_eq!(Balances::reserved_balance(&9), 0);
+        assert_eq!(Balances::free_balance(&9), 11);
+    });
+}
+
+#[test]
+fn unsponsored_applications_expire() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let id = apply(8, 10, 10);
+        assert_eq!(Protoshine::proposal_expiries(6), vec![id]);
+        assert_eq!(Balances::reserved_balance(&8), 2);
+
+        <Protoshine as OnInitialize<u64>>::on_initialize(5);
+        assert!(Protoshine::membership_applications(id).is_some());
+
+        <Protoshine as OnInitialize<u64>>::on_initialize(6);
+        assert!(Protoshine::membership_applications(id).is_none());
+        assert!(Protoshine::proposal_expiries(6).is_empty());
+        assert_eq!(Balances::reserved_balance(&8), 0);
+        assert_eq!(Balances::free_balance(&8), 616);
+    });
+}
+
+#[test]
+fn sponsored_applications_are_not_expired_by_application_time_limit() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let id = apply(8, 10, 10);
+        System::set_block_number(4);
+        assert_ok!(Protoshine::sponsor_proposal(
+            Origin::signed(1),
+            id,
+            VoteWeighting::Linear
+        ));
+        assert_eq!(Protoshine::vote_expiries(8), vec![id]);
+        assert_ok!(Protoshine::vote_on_proposal(
+            Origin::signed(2),
+            id,
+            Vote::Against(5),
+            Conviction::None
+        ));
+
+        <Protoshine as OnInitialize<u64>>::on_initialize(6);
+        assert_eq!(
+            Protoshine::membership_applications(id).unwrap().stage,
+            ProposalStage::Voting
+        );
+
+        // voting period ends without passage so the proposal is rejected
+        <Protoshine as OnInitialize<u64>>::on_initialize(8);
+        assert_eq!(
+            Protoshine::membership_applications(id).unwrap().stage,
+            ProposalStage::Rejected
+        );
+        assert!(Protoshine::vote_states(id).is_some());
+        // 50% of the bond is slashed to the bank
+        assert_eq!(Balances::reserved_balance(&8), 0);
+        assert_eq!(Balances::free_balance(&8), 615);
+        assert_eq!(Protoshine::vote_releases(), vec![id]);
+        <Protoshine as OnFinalize<u64>>::on_finalize(8);
+        assert_eq!(
+            Protoshine::membership_shares(&1).unwrap().reserved_shares,
+            0
+        );
+        assert!(Protoshine::votes_by_members(id, &1).is_none());
+        // the rejected proposal is pruned once its votes are released
+        assert!(Protoshine::membership_applications(id).is_none());
+        assert!(Protoshine::vote_states(id).is_none());
+    });
+}
+
+#[test]
+fn expiring_votes_are_decided_against_the_current_electorate() {
+    new_test_ext().execute_with(|| {
+        let id = apply(8, 10, 10);
+        assert_ok!(Protoshine::sponsor_proposal(
+            Origin::signed(1),
+            id,
+            VoteWeighting::Linear
+        ));
+        // half of the shares must vote
+        <VoteStates<Test>>::mutate(id, |state| {
+            if let Some(state) = state {
+                state.threshold = VoteThreshold::TurnoutBiased {
+                    threshold: Permill::from_percent(67),
+                    quorum: Permill::from_percent(50),
+                };
+            }
+        });
+        for member in 2..4 {
+            assert_ok!(Protoshine::vote_on_proposal(
+                Origin::signed(member),
+                id,
+                Vote::InFavor(10),
+                Conviction::None
+            ));
+        }
+
+        // 23 shares voted, short of the quorum of 30 until 20 shares are burned
+        assert_ok!(Protoshine::leave_membership(Origin::signed(5), 10));
+        assert_ok!(Protoshine::leave_membership(Origin::signed(6), 10));
+        assert_eq!(Protoshine::vote_states(id).unwrap().all_voters, 40);
+        assert_eq!(
+            Protoshine::membership_applications(id).unwrap().stage,
+            ProposalStage::Voting
+        );
+
+        <Protoshine as OnInitialize<u64>>::on_initialize(4);
+        assert_eq!(
+            Protoshine::membership_applications(id).unwrap().stage,
+            ProposalStage::Passed
+        );
+        assert_eq!(Protoshine::membership_approvals(), vec![id]);
+    });
+}
+
+#[test]
+fn membership_votes_stay_open_while_votes_against_can_be_retracted() {
+    new_test_ext().execute_with(|| {
+        let id = apply(8, 10, 10);
+        assert_ok!(Protoshine::sponsor_proposal(
+            Origin::signed(1),
+            id,
+            VoteWeighting::Linear
+        ));
+        // every other member is against but any of them can still change their mind
+        for member in 2..7 {
+            assert_ok!(Protoshine::vote_on_proposal(
+                Origin::signed(member),
+                id,
+                Vote::Against(10),
+                Conviction::None
+            ));
+            assert_eq!(
+                Protoshine::membership_applications(id).unwrap().stage,
+                ProposalStage::Voting
+            );
+        }
+        assert_ok!(Protoshine::retract_vote(Origin::signed(2), id));
+        assert_ok!(Protoshine::vote_on_proposal(
+            Origin::signed(2),
+            id,
+            Vote::InFavor(10),
+            Conviction::None
+        ));
+        assert_eq!(
+            Protoshine::membership_applications(id).unwrap().stage,
+            ProposalStage::Voting
+        );
+
+        // 13 in favor against 40 when the voting period ends so the proposal is rejected
+        <Protoshine as OnInitialize<u64>>::on_initialize(4);
+        assert_eq!(
+            Protoshine::membership_applications(id).unwrap().stage,
+            ProposalStage::Rejected
+        );
+        assert!(Protoshine::open_votes().is_empty());
+        assert!(Protoshine::membership_approvals().is_empty());
+        assert_eq!(Protoshine::vote_releases(), vec![id]);
+        assert_eq!(Balances::reserved_balance(&8), 0);
+        assert_eq!(Balances::free_balance(&8), 615);
+        assert_eq!(
+            Protoshine::bank_balance(Protoshine::bank_account()).unwrap(),
+            61
+        );
+
+        // the vote is closed
+        assert_noop!(
+            Protoshine::vote_on_proposal(Origin::signed(6), id, Vote::InFavor(5), Conviction::None),
+            Error::<Test>::RequestInWrongStage
+        );
+    });
+}
+
+#[test]
+fn open_votes_track_the_shares_issued_and_burned() {
+    new_test_ext().execute_with(|| {
+        let first = apply(8, 10, 10);
+        sponsor_and_pass::<MembershipProposalOf<Test>>(first);
+        let second = apply(3, 5, 5);
+        assert_ok!(Protoshine::sponsor_proposal(
+            Origin::signed(1),
+            second,
+            VoteWeighting::Linear
+        ));
+        assert_eq!(Protoshine::open_votes(), vec![second]);
+        assert_eq!(Protoshine::vote_states(second).unwrap().all_voters, 60);
 
-        // let _ = Protoshine::membership_application(eight, 5, 10);
+        // the first proposal's shares are issued while the second vote is open
+        <Protoshine as OnFinalize<u64>>::on_finalize(2);
+        assert_eq!(Protoshine::bank_account().shares, 70);
+        assert_eq!(Protoshine::vote_states(second).unwrap().all_voters, 70);
+        assert_eq!(
+            Protoshine::vote_states(second).unwrap().electorate_weight,
+            70
+        );
 
-        // // ratio > banks_collateral_ratio
-        // // => bond = 4 * MembershipProposalBond constant = 4 * 1 = 4
-        // // 613 - 4 = 608
-        // assert_eq!(Balances::free_balance(&8), 608);
+        assert_ok!(Protoshine::leave_membership(Origin::signed(6), 4));
+        assert_eq!(Protoshine::vote_states(second).unwrap().all_voters, 66);
+        assert_eq!(
+            Protoshine::vote_states(second).unwrap().electorate_weight,
+            66
+        );
+    });
+}
+
+#[test]
+fn membership_votes_record_the_consensus_threshold() {
+    new_test_ext().execute_with(|| {
+        let id = apply(8, 10, 10);
+        assert_ok!(Protoshine::sponsor_proposal(
+            Origin::signed(1),
+            id,
+            VoteWeighting::Linear
+        ));
+        assert_eq!(
+            Protoshine::vote_states(id).unwrap().threshold,
+            VoteThreshold::TurnoutBiased {
+                threshold: Permill::from_percent(67),
+                quorum: Permill::from_percent(10),
+            }
+        );
+    });
+}
+
+#[test]
+fn turnout_biased_threshold_falls_as_turnout_rises() {
+    // quorum of 50 shares, 60% of which (30 shares) must be in favor
+    let state = |in_favor, against| VotingState {
+        in_favor,
+        against,
+        turnout: in_favor + against,
+        all_voters: 100,
+        electorate_weight: 100,
+        threshold: VoteThreshold::TurnoutBiased {
+            threshold: Permill::from_percent(60),
+            quorum: Permill::from_percent(50),
+        },
+        weighting: VoteWeighting::Linear,
+    };
+    // below quorum
+    assert!(!state(30, 19).approved());
+    // 60% at the quorum
+    assert!(state(30, 20).approved());
+    assert!(!state(29, 21).approved());
+    // less than 60% is enough once turnout rises
+    assert!(state(30, 25).approved());
+    // but never less than a simple majority
+    assert!(!state(35, 35).approved());
+    // abstaining shares count towards the quorum
+    assert!(!state(30, 0).approved());
+    let abstained = VotingState {
+        turnout: 50,
+        ..state(30, 0)
+    };
+    assert!(abstained.approved());
+}
+
+#[test]
+fn quorum_consensus_is_at_least_as_strict_as_turnout_biased() {
+    let state = |in_favor, against, threshold| VotingState {
+        in_favor,
+        against,
+        turnout: in_favor + against,
+        all_voters: 100,
+        electorate_weight: 100,
+        threshold,
+        weighting: VoteWeighting::Linear,
+    };
+    let biased = VoteThreshold::TurnoutBiased {
+        threshold: Permill::from_percent(60),
+        quorum: Permill::from_percent(50),
+    };
+    let consensus = VoteThreshold::QuorumConsensus {
+        threshold: Permill::from_percent(60),
+        quorum: Permill::from_percent(50),
+    };
+    // the same at the quorum
+    assert!(state(30, 20, consensus.clone()).approved());
+    assert!(!state(29, 21, consensus.clone()).approved());
+    assert!(!state(30, 19, consensus.clone()).approved());
+    // but 60% of the turnout is still required once turnout rises
+    assert!(state(30, 25, biased.clone()).approved());
+    assert!(!state(30, 25, consensus.clone()).approved());
+    assert!(state(60, 40, consensus.clone()).approved());
+    for in_favor in 0..=100 {
+        for against in 0..=(100 - in_favor) {
+            if state(in_favor, against, consensus.clone()).approved() {
+                assert!(state(in_favor, against, biased.clone()).approved());
+            }
+        }
+    }
+    // the weights are summed without overflowing
+    let weighted = |in_favor, against| VotingState {
+        in_favor,
+        against,
+        turnout: 100,
+        ..state(0, 0, consensus.clone())
+    };
+    assert!(!weighted(Shares::max_value(), Shares::max_value()).approved());
+    assert!(weighted(Shares::max_value(), Shares::max_value() / 3).approved());
+}
+
+#[test]
+fn conviction_multiplies_vote_weight_and_locks_reserved_shares() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let id = apply(8, 10, 10);
+        assert_ok!(Protoshine::sponsor_proposal(
+            Origin::signed(1),
+            id,
+            VoteWeighting::Linear
+        ));
+        assert_ok!(Protoshine::vote_on_proposal(
+            Origin::signed(2),
+            id,
+            Vote::Against(5),
+            Conviction::Locked4x
+        ));
+        let state = Protoshine::vote_states(id).unwrap();
+        assert_eq!((state.in_favor, state.against, state.turnout), (3, 20, 8));
+        assert_noop!(
+            Protoshine::vote_on_proposal(Origin::signed(2), id, Vote::Against(1), Conviction::None),
+            Error::<Test>::ConvictionCannotBeLowered
+        );
+        assert_ok!(Protoshine::vote_on_proposal(
+            Origin::signed(3),
+            id,
+            Vote::InFavor(10),
+            Conviction::Locked2x
+        ));
+        // 63 of 83 in favor
+        assert_ok!(Protoshine::vote_on_proposal(
+            Origin::signed(4),
+            id,
+            Vote::InFavor(10),
+            Conviction::Locked4x
+        ));
+        System::set_block_number(5);
+        <Protoshine as OnInitialize<u64>>::on_initialize(5);
+        assert_eq!(
+            Protoshine::membership_applications(id).unwrap().stage,
+            ProposalStage::Passed
+        );
+
+        // 4 and 3 are locked for 3 and 1 periods of 3 blocks
+        <Protoshine as OnFinalize<u64>>::on_finalize(5);
+        assert_eq!(Protoshine::conviction_unlocks(14), vec![(id, 4, 10)]);
+        assert_eq!(Protoshine::conviction_unlocks(8), vec![(id, 3, 10)]);
+        // 2 is locked and the sponsor is released right away
+        System::set_block_number(6);
+        <Protoshine as OnFinalize<u64>>::on_finalize(6);
+        assert_eq!(Protoshine::conviction_unlocks(15), vec![(id, 2, 5)]);
+        assert_eq!(
+            Protoshine::membership_shares(&1).unwrap().reserved_shares,
+            0
+        );
+        assert_eq!(
+            Protoshine::membership_shares(&3).unwrap().reserved_shares,
+            10
+        );
+
+        <Protoshine as OnInitialize<u64>>::on_initialize(8);
+        assert_eq!(
+            Protoshine::membership_shares(&3).unwrap().reserved_shares,
+            0
+        );
+        assert_eq!(
+            Protoshine::membership_shares(&2).unwrap().reserved_shares,
+            5
+        );
+        <Protoshine as OnInitialize<u64>>::on_initialize(14);
+        assert_eq!(
+            Protoshine::membership_shares(&4).unwrap().reserved_shares,
+            0
+        );
+        assert_eq!(
+            Protoshine::membership_shares(&2).unwrap().reserved_shares,
+            5
+        );
+        <Protoshine as OnInitialize<u64>>::on_initialize(15);
+        assert_eq!(
+            Protoshine::membership_shares(&2).unwrap().reserved_shares,
+            0
+        );
+    });
+}
+
+#[test]
+fn quadratic_votes_weigh_the_square_root_of_aggregated_shares() {
+    new_test_ext().execute_with(|| {
+        let id = apply(8, 10, 10);
+        assert_ok!(Protoshine::sponsor_proposal(
+            Origin::signed(1),
+            id,
+            VoteWeighting::Quadratic
+        ));
+        assert_eq!(Protoshine::vote_states(id).unwrap().in_favor, 1);
+        assert_ok!(Protoshine::vote_on_proposal(
+            Origin::signed(2),
+            id,
+            Vote::Against(4),
+            Conviction::None
+        ));
+        assert_eq!(Protoshine::vote_states(id).unwrap().against, 2);
+        // splitting 9 shares into 4 + 5 weighs 3 rather than 2 + 2
+        assert_ok!(Protoshine::vote_on_proposal(
+            Origin::signed(2),
+            id,
+            Vote::Against(5),
+            Conviction::None
+        ));
+        let state = Protoshine::vote_states(id).unwrap();
+        assert_eq!((state.in_favor, state.against, state.turnout), (1, 3, 12));
+        assert_eq!(
+            Protoshine::membership_shares(&2).unwrap().reserved_shares,
+            9
+        );
+
+        assert_ok!(Protoshine::vote_on_proposal(
+            Origin::signed(3),
+            id,
+            Vote::InFavor(9),
+            Conviction::None
+        ));
+        assert_eq!(
+            Protoshine::membership_applications(id).unwrap().stage,
+            ProposalStage::Voting
+        );
+        // 7 of 10 in favor
+        assert_ok!(Protoshine::vote_on_proposal(
+            Origin::signed(4),
+            id,
+            Vote::InFavor(9),
+            Conviction::None
+        ));
+        <Protoshine as OnInitialize<u64>>::on_initialize(4);
+        assert_eq!(
+            Protoshine::membership_applications(id).unwrap().stage,
+            ProposalStage::Passed
+        );
+    });
+}
+
+#[test]
+fn quadratic_votes_are_held_to_a_quadratic_quorum() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let change = ParameterChange::BatchPeriod(3);
+        assert_ok!(Protoshine::meta_proposal(Origin::signed(1), change.clone()));
+        let id = Protoshine::meta_proposal_id(&1, &change, 0);
+        assert_ok!(Protoshine::sponsor_proposal(
+            Origin::signed(1),
+            id,
+            VoteWeighting::Quadratic
+        ));
+        // six members with 10 shares weigh 3 each
+        assert_eq!(Protoshine::vote_states(id).unwrap().electorate_weight, 18);
+        for voter in 2..7 {
+            assert_ok!(Protoshine::vote_on_proposal(
+                Origin::signed(voter),
+                id,
+                Vote::InFavor(10),
+                Conviction::None
+            ));
+        }
+        // 16 in favor clears 75% of a 50% quorum of 18 while 75% of 50% of the 60 shares, 22.5,
+        // would be out of reach of even a unanimous vote
+        let state = Protoshine::vote_states(id).unwrap();
+        assert_eq!((state.in_favor, state.turnout), (16, 53));
+        <Protoshine as OnInitialize<u64>>::on_initialize(5);
+        assert_eq!(
+            Protoshine::meta_proposals(id).unwrap().stage,
+            ProposalStage::Law
+        );
+        assert_eq!(Protoshine::batch_period(), 3);
+    });
+}
+
+#[test]
+fn abstain_votes_count_towards_turnout_only() {
+    new_test_ext().execute_with(|| {
+        let id = apply(8, 10, 10);
+        assert_ok!(Protoshine::sponsor_proposal(
+            Origin::signed(1),
+            id,
+            VoteWeighting::Linear
+        ));
+        assert_ok!(Protoshine::vote_on_proposal(
+            Origin::signed(2),
+            id,
+            Vote::Against(5),
+            Conviction::None
+        ));
+        assert_noop!(
+            Protoshine::vote_on_proposal(
+                Origin::signed(3),
+                id,
+                Vote::Abstain(5),
+                Conviction::Locked2x
+            ),
+            Error::<Test>::ConvictionOnAbstain
+        );
+        assert_ok!(Protoshine::vote_on_proposal(
+            Origin::signed(3),
+            id,
+            Vote::Abstain(5),
+            Conviction::None
+        ));
+        let state = Protoshine::vote_states(id).unwrap();
+        assert_eq!((state.in_favor, state.against, state.turnout), (3, 5, 13));
+        assert_eq!(
+            Protoshine::membership_shares(&3).unwrap().reserved_shares,
+            5
+        );
+
+        // 2 replaces their vote against with an abstention so nobody votes against
+        assert_ok!(Protoshine::vote_on_proposal(
+            Origin::signed(2),
+            id,
+            Vote::Abstain(5),
+            Conviction::None
+        ));
+        let state = Protoshine::vote_states(id).unwrap();
+        assert_eq!((state.in_favor, state.against, state.turnout), (3, 0, 13));
+        assert_eq!(
+            Protoshine::membership_shares(&2).unwrap().reserved_shares,
+            5
+        );
+        // but the 3 shares in favor fall short of 67% of the quorum of 6 shares
+        <Protoshine as OnInitialize<u64>>::on_initialize(4);
+        assert_eq!(
+            Protoshine::membership_applications(id).unwrap().stage,
+            ProposalStage::Rejected
+        );
+    });
+}
+
+#[test]
+fn retracted_votes_are_reversed_and_released() {
+    new_test_ext().execute_with(|| {
+        let id = apply(8, 10, 10);
+        assert_ok!(Protoshine::sponsor_proposal(
+            Origin::signed(1),
+            id,
+            VoteWeighting::Linear
+        ));
+        assert_ok!(Protoshine::vote_on_proposal(
+            Origin::signed(2),
+            id,
+            Vote::Against(5),
+            Conviction::Locked2x
+        ));
+        assert_eq!(Protoshine::vote_states(id).unwrap().against, 10);
+        assert_noop!(
+            Protoshine::retract_vote(Origin::signed(3), id),
+            Error::<Test>::NoVoteToRetract
+        );
+        assert_noop!(
+            Protoshine::retract_vote(Origin::signed(1), id),
+            Error::<Test>::CannotRetractSponsorVote
+        );
+
+        assert_ok!(Protoshine::retract_vote(Origin::signed(2), id));
+        let state = Protoshine::vote_states(id).unwrap();
+        assert_eq!((state.in_favor, state.against, state.turnout), (3, 0, 3));
+        assert!(Protoshine::votes_by_members(id, &2).is_none());
+        assert_eq!(Protoshine::vote_conviction(id, &2), Conviction::None);
+        assert_eq!(Protoshine::voters(id), vec![1]);
+        // released right away despite the conviction
+        assert_eq!(
+            Protoshine::membership_shares(&2).unwrap().reserved_shares,
+            0
+        );
+        // the vote stays open until its `VotingPeriod` ends
+        assert_eq!(
+            Protoshine::membership_applications(id).unwrap().stage,
+            ProposalStage::Voting
+        );
+    });
+}
+
+#[test]
+fn sponsors_can_only_add_to_their_vote_in_favor() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let id = apply(8, 10, 10);
+        assert_ok!(Protoshine::sponsor_proposal(
+            Origin::signed(1),
+            id,
+            VoteWeighting::Linear
+        ));
+        for vote in vec![Vote::Against(1), Vote::Abstain(1)] {
+            assert_noop!(
+                Protoshine::vote_on_proposal(Origin::signed(1), id, vote, Conviction::None),
+                Error::<Test>::CannotReplaceSponsorVote
+            );
+        }
+        assert_ok!(Protoshine::vote_on_proposal(
+            Origin::signed(1),
+            id,
+            Vote::InFavor(2),
+            Conviction::None
+        ));
+        assert_eq!(Protoshine::votes_by_members(id, &1), Some(Vote::InFavor(5)));
+        // the event carries the whole vote rather than the increment
+        assert_eq!(
+            protoshine_events().last(),
+            Some(&RawEvent::Voted(id, 1, Vote::InFavor(5), Conviction::None))
+        );
+        assert_eq!(Protoshine::vote_states(id).unwrap().in_favor, 5);
+        assert_eq!(
+            Protoshine::membership_shares(&1).unwrap().reserved_shares,
+            5
+        );
+    });
+}
+
+/// In favor, against and turnout totals computed from scratch from each member's vote
+fn tally<'a>(
+    state: &VotingState,
+    votes: impl Iterator<Item = &'a (Vote, Conviction)>,
+) -> (Shares, Shares, Shares) {
+    votes.fold(
+        (0, 0, 0),
+        |(in_favor, against, turnout), (vote, conviction)| {
+            let weight = state.weight(*conviction, vote.inner());
+            match vote {
+                Vote::InFavor(shares) => (in_favor + weight, against, turnout + shares),
+                Vote::Against(shares) => (in_favor, against + weight, turnout + shares),
+                Vote::Abstain(shares) => (in_favor, against, turnout + shares),
+            }
+        },
+    )
+}
+
+#[test]
+fn vote_state_totals_equal_the_sum_of_member_votes() {
+    // every sequence of three votes by two members
+    let mut choices = Vec::new();
+    for member in 0..2usize {
+        for shares in [1u32, 4].iter() {
+            for vote in [
+                Vote::InFavor(*shares),
+                Vote::Against(*shares),
+                Vote::Abstain(*shares),
+            ]
+            .iter()
+            {
+                for conviction in [Conviction::None, Conviction::Locked2x].iter() {
+                    choices.push((member, vote.clone(), *conviction));
+                }
+            }
+        }
+    }
+    for weighting in [VoteWeighting::Linear, VoteWeighting::Quadratic].iter() {
+        let initial = VotingState {
+            in_favor: 0,
+            against: 0,
+            turnout: 0,
+            all_voters: 100,
+            electorate_weight: 100,
+            threshold: VoteThreshold::SimpleMajority,
+            weighting: *weighting,
+        };
+        for first in choices.iter() {
+            for second in choices.iter() {
+                for third in choices.iter() {
+                    let mut state = initial.clone();
+                    let mut ledger: [Option<(Vote, Conviction)>; 2] = [None, None];
+                    for (member, vote, conviction) in [first, second, third].iter() {
+                        let previous = ledger[*member].clone();
+                        match state.apply(previous.clone(), (vote.clone(), *conviction)) {
+                            Ok((next, share_delta)) => {
+                                let combined = Vote::combine(
+                                    previous.as_ref().map(|(old, _)| old),
+                                    vote.clone(),
+                                )
+                                .unwrap();
+                                let old_shares = previous.map(|(old, _)| old.inner()).unwrap_or(0);
+                                assert_eq!(share_delta.applied_to(old_shares), combined.inner());
+                                ledger[*member] = Some((combined, *conviction));
+                                state = next;
+                            }
+                            Err(error) => assert!(
+                                error == VoteError::ConvictionLowered
+                                    || error == VoteError::ConvictionOnAbstain
+                            ),
+                        }
+                        assert_eq!(
+                            tally(&state, ledger.iter().flatten()),
+                            (state.in_favor, state.against, state.turnout)
+                        );
+                    }
+                    // retracting every vote empties the tally
+                    for vote in ledger.iter().flatten() {
+                        state = state.retract(vote.clone()).unwrap().0;
+                    }
+                    assert_eq!((state.in_favor, state.against, state.turnout), (0, 0, 0));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn membership_vote_state_matches_votes_by_members() {
+    new_test_ext().execute_with(|| {
+        let id = apply(8, 10, 10);
+        assert_ok!(Protoshine::sponsor_proposal(
+            Origin::signed(1),
+            id,
+            VoteWeighting::Quadratic
+        ));
+        // every share must vote for the proposal to pass so it stays open
+        <VoteStates<Test>>::mutate(id, |state| {
+            if let Some(state) = state {
+                state.threshold = VoteThreshold::TurnoutBiased {
+                    threshold: Permill::from_percent(50),
+                    quorum: Permill::one(),
+                };
+            }
+        });
+
+        // deterministic pseudo-random votes, changes and retractions
+        let mut seed: u64 = 42;
+        let mut next = |bound: u64| {
+            seed = seed
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (seed >> 33) % bound
+        };
+        for _ in 0..100 {
+            if Protoshine::membership_applications(id).unwrap().stage != ProposalStage::Voting {
+                break;
+            }
+            let member = 2 + next(5);
+            let shares = 1 + next(2) as Shares;
+            let conviction = if next(4).is_zero() {
+                Conviction::Locked2x
+            } else {
+                Conviction::None
+            };
+            // invalid transitions fail without changing storage
+            let _ = match next(4) {
+                0 => Protoshine::vote_on_proposal(
+                    Origin::signed(member),
+                    id,
+                    Vote::InFavor(shares),
+                    conviction,
+                ),
+                1 => Protoshine::vote_on_proposal(
+                    Origin::signed(member),
+                    id,
+                    Vote::Against(shares),
+                    conviction,
+                ),
+                2 => Protoshine::vote_on_proposal(
+                    Origin::signed(member),
+                    id,
+                    Vote::Abstain(shares),
+                    Conviction::None,
+                ),
+                _ => Protoshine::retract_vote(Origin::signed(member), id),
+            };
+
+            let state = Protoshine::vote_states(id).unwrap();
+            let votes: Vec<(Vote, Conviction)> = Protoshine::voters(id)
+                .iter()
+                .map(|voter| {
+                    (
+                        Protoshine::votes_by_members(id, voter).unwrap(),
+                        Protoshine::vote_conviction(id, voter),
+                    )
+                })
+                .collect();
+            assert_eq!(
+                tally(&state, votes.iter()),
+                (state.in_favor, state.against, state.turnout)
+            );
+            for member in 2..7 {
+                assert_eq!(
+                    Protoshine::membership_shares(&member)
+                        .unwrap()
+                        .reserved_shares,
+                    Protoshine::votes_by_members(id, &member).map_or(0, |vote| vote.inner())
+                );
+            }
+        }
+    });
+}
+
+#[test]
+fn votes_are_capped_at_shares_held_when_the_vote_opened() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        // an existing member and a new member are issued shares
+        let more_shares = apply(3, 10, 10);
+        sponsor_and_pass::<MembershipProposalOf<Test>>(more_shares);
+        let new_member = apply(9, 10, 10);
+        sponsor_and_pass::<MembershipProposalOf<Test>>(new_member);
+        let id = apply(8, 10, 10);
+
+        // the shares are issued at the end of the block in which the vote opens
+        System::set_block_number(2);
+        assert_ok!(Protoshine::sponsor_proposal(
+            Origin::signed(4),
+            id,
+            VoteWeighting::Linear
+        ));
+        assert_eq!(Protoshine::vote_snapshot(id), Some(1));
+        <Protoshine as OnFinalize<u64>>::on_finalize(2);
+        assert_eq!(Protoshine::membership_shares(&3).unwrap().total_shares, 20);
+        assert_eq!(Protoshine::share_checkpoints(&3), vec![(0, 10), (2, 20)]);
+        assert_eq!(Protoshine::shares_at(&3, 1), 10);
+        assert_eq!(Protoshine::shares_at(&9, 1), 0);
+
+        assert_noop!(
+            Protoshine::vote_on_proposal(Origin::signed(9), id, Vote::InFavor(1), Conviction::None),
+            Error::<Test>::InsufficientMembershipVoteCollateral
+        );
+        assert_noop!(
+            Protoshine::vote_on_proposal(
+                Origin::signed(3),
+                id,
+                Vote::InFavor(11),
+                Conviction::None
+            ),
+            Error::<Test>::InsufficientMembershipVoteCollateral
+        );
+        assert_ok!(Protoshine::vote_on_proposal(
+            Origin::signed(3),
+            id,
+            Vote::InFavor(10),
+            Conviction::None
+        ));
+        // the snapshot is dropped once the vote closes
+        <Protoshine as OnInitialize<u64>>::on_initialize(6);
+        assert!(Protoshine::vote_snapshot(id).is_none());
+
+        // checkpoints that no snapshot can read anymore are pruned
+        System::set_block_number(8);
+        assert_ok!(Protoshine::leave_membership(Origin::signed(3), 1));
+        assert_eq!(Protoshine::share_checkpoints(&3), vec![(2, 20), (8, 19)]);
+    });
+}
+
+#[test]
+fn delegated_shares_are_reserved_from_the_delegator_when_used() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Protoshine::delegate_shares(Origin::signed(2), 3, 5));
+        assert_eq!(Protoshine::delegators_of(&3), vec![2]);
+        // delegating doesn't reserve anything
+        assert_eq!(
+            Protoshine::membership_shares(&2).unwrap().reserved_shares,
+            0
+        );
+
+        let id = apply(8, 10, 10);
+        assert_ok!(Protoshine::sponsor_proposal(
+            Origin::signed(1),
+            id,
+            VoteWeighting::Linear
+        ));
+        // 10 own shares and 5 delegated shares
+        assert_noop!(
+            Protoshine::vote_on_proposal(
+                Origin::signed(3),
+                id,
+                Vote::InFavor(16),
+                Conviction::None
+            ),
+            Error::<Test>::InsufficientMembershipVoteCollateral
+        );
+        assert_ok!(Protoshine::vote_on_proposal(
+            Origin::signed(3),
+            id,
+            Vote::InFavor(15),
+            Conviction::None
+        ));
+        assert_eq!(
+            Protoshine::membership_shares(&3).unwrap().reserved_shares,
+            10
+        );
+        assert_eq!(
+            Protoshine::membership_shares(&2).unwrap().reserved_shares,
+            5
+        );
+        assert_eq!(Protoshine::delegation(&2).unwrap().drawn, 5);
+        assert_eq!(Protoshine::delegated_vote_draws(id, &3), vec![(2, 5)]);
+        assert_noop!(
+            Protoshine::undelegate(Origin::signed(2)),
+            Error::<Test>::DelegatedSharesInUse
+        );
+
+        // the vote passed so the drawn shares go back to the delegator
+        <Protoshine as OnInitialize<u64>>::on_initialize(4);
+        <Protoshine as OnFinalize<u64>>::on_finalize(1);
+        assert_eq!(
+            Protoshine::membership_shares(&3).unwrap().reserved_shares,
+            0
+        );
+        assert_eq!(
+            Protoshine::membership_shares(&2).unwrap().reserved_shares,
+            0
+        );
+        assert_eq!(Protoshine::delegation(&2).unwrap().drawn, 0);
+        assert_ok!(Protoshine::undelegate(Origin::signed(2)));
+        assert!(Protoshine::delegation(&2).is_none());
+        assert!(Protoshine::delegators_of(&3).is_empty());
+    });
+}
+
+#[test]
+fn delegation_is_a_single_hop() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Protoshine::delegate_shares(Origin::signed(2), 2, 5),
+            Error::<Test>::CannotDelegateToSelf
+        );
+        assert_noop!(
+            Protoshine::delegate_shares(Origin::signed(2), 8, 5),
+            Error::<Test>::DelegateNotAMember
+        );
+        assert_noop!(
+            Protoshine::delegate_shares(Origin::signed(2), 3, 0),
+            Error::<Test>::InvalidDelegation
+        );
+        assert_noop!(
+            Protoshine::delegate_shares(Origin::signed(2), 3, 11),
+            Error::<Test>::InsufficientUnreservedShares
+        );
+        assert_noop!(
+            Protoshine::undelegate(Origin::signed(2)),
+            Error::<Test>::NoDelegation
+        );
+        assert_ok!(Protoshine::delegate_shares(Origin::signed(2), 3, 5));
+        assert_noop!(
+            Protoshine::delegate_shares(Origin::signed(2), 4, 5),
+            Error::<Test>::AlreadyDelegated
+        );
+        assert_ok!(Protoshine::delegate_shares(Origin::signed(3), 4, 5));
+
+        let id = apply(8, 10, 10);
+        assert_ok!(Protoshine::sponsor_proposal(
+            Origin::signed(1),
+            id,
+            VoteWeighting::Linear
+        ));
+        // 4 can only draw on 3's own shares, not on the shares 2 delegated to 3
+        assert_noop!(
+            Protoshine::vote_on_proposal(
+                Origin::signed(4),
+                id,
+                Vote::Against(16),
+                Conviction::None
+            ),
+            Error::<Test>::InsufficientMembershipVoteCollateral
+        );
+        assert_ok!(Protoshine::vote_on_proposal(
+            Origin::signed(4),
+            id,
+            Vote::Against(15),
+            Conviction::None
+        ));
+        assert_eq!(Protoshine::delegated_vote_draws(id, &4), vec![(3, 5)]);
+        assert_eq!(
+            Protoshine::membership_shares(&2).unwrap().reserved_shares,
+            0
+        );
+    });
+}
+
+#[test]
+fn applicants_can_withdraw_unsponsored_applications() {
+    new_test_ext().execute_with(|| {
+        let id = apply(8, 10, 10);
+        assert_eq!(Balances::reserved_balance(&8), 2);
+        assert_err!(
+            Protoshine::withdraw_membership_application(Origin::signed(9), id),
+            Error::<Test>::NotTheApplicant
+        );
+
+        assert_ok!(Protoshine::withdraw_membership_application(
+            Origin::signed(8),
+            id
+        ));
+        assert!(Protoshine::membership_applications(id).is_none());
+        assert_eq!(Balances::reserved_balance(&8), 0);
+        assert_eq!(Balances::free_balance(&8), 616);
+        assert_err!(
+            Protoshine::withdraw_membership_application(Origin::signed(8), id),
+            Error::<Test>::IndexWithNoAssociatedMembershipProposal
+        );
+    });
+}
+
+#[test]
+fn sponsored_applications_cannot_be_withdrawn() {
+    new_test_ext().execute_with(|| {
+        let id = apply(8, 10, 10);
+        assert_ok!(Protoshine::sponsor_proposal(
+            Origin::signed(1),
+            id,
+            VoteWeighting::Linear
+        ));
+        assert_err!(
+            Protoshine::withdraw_membership_application(Origin::signed(8), id),
+            Error::<Test>::CannotWithdrawSponsoredApplication
+        );
+        assert_eq!(Balances::reserved_balance(&8), 2);
+    });
+}
+
+#[test]
+fn legacy_u32_proposals_are_migrated_to_hash_ids() {
+    use frame_support::storage::migration::{put_storage_value, take_storage_value};
+    use frame_support::{Blake2_256, StorageHasher, StorageValue, Twox64Concat};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        // same encodings as the `MembershipProposal`, `VotingState` and `Vote` that
+        // were keyed by `u32`, before bonds, turnout and conviction were stored
+        let legacy_key = |index: u32| index.using_encoded(Blake2_256::hash).to_vec();
+        let passed = (1u32, 8u64, 10u64, 10u32, ProposalStage::Passed, 1u64);
+        put_storage_value(
+            b"Protoshine",
+            b"MembershipApplications",
+            &legacy_key(1),
+            passed,
+        );
+        let voting = (2u32, 9u64, 5u64, 5u32, ProposalStage::Voting, 1u64);
+        put_storage_value(
+            b"Protoshine",
+            b"MembershipApplications",
+            &legacy_key(2),
+            voting,
+        );
+        // the legacy tally drifted from the votes below
+        let legacy_state = (9u32, 2u32, 1u32, VoteThreshold::SimpleMajority);
+        put_storage_value(
+            b"Protoshine",
+            b"MembershipVoteStates",
+            &legacy_key(2),
+            legacy_state,
+        );
+        let vote_key = |voter: u64| {
+            let mut key = legacy_key(2);
+            key.extend(voter.using_encoded(Twox64Concat::hash));
+            key
+        };
+        put_storage_value(
+            b"Protoshine",
+            b"VotesByMembers",
+            &vote_key(1),
+            Vote::InFavor(3),
+        );
+        put_storage_value(
+            b"Protoshine",
+            b"VotesByMembers",
+            &vote_key(2),
+            Vote::InFavor(5),
+        );
+        // a closed vote whose voter's shares are still reserved
+        let rejected = (3u32, 10u64, 5u64, 5u32, ProposalStage::Rejected, 1u64);
+        put_storage_value(
+            b"Protoshine",
+            b"MembershipApplications",
+            &legacy_key(3),
+            rejected,
+        );
+        let mut rejected_vote_key = legacy_key(3);
+        rejected_vote_key.extend(3u64.using_encoded(Twox64Concat::hash));
+        put_storage_value(
+            b"Protoshine",
+            b"VotesByMembers",
+            &rejected_vote_key,
+            Vote::Against(4),
+        );
+        <MembershipShares<Test>>::mutate(&3, |profile| {
+            profile.as_mut().unwrap().reserved_shares = 4;
+        });
+        put_storage_value(b"Protoshine", b"MembershipApprovals", &[], vec![1u32]);
+        put_storage_value(b"Protoshine", b"MembershipApplicationCount", &[], 3u32);
+        put_storage_value(b"Protoshine", b"StorageVersion", &[], Version::V1);
+        // parameters weren't stored before `Version::V4`
+        <crate::BatchPeriod<Test>>::kill();
+
+        // `MaximumVoteReleases` (2) legacy proposals are migrated per block
+        <Protoshine as OnInitialize<u64>>::on_initialize(1);
+        assert_eq!(Protoshine::storage_version(), Version::V1);
+        let id = Protoshine::membership_proposal_id(&8, 10, 10, 0);
+        assert_eq!(Protoshine::vote_releases(), vec![id]);
+        assert_noop!(
+            Protoshine::membership_application(Origin::signed(9), 5, 5),
+            Error::<Test>::StorageMigrationInProgress
+        );
+        // the module waits for the migration to finish
+        <Protoshine as OnFinalize<u64>>::on_finalize(1);
+        assert_eq!(Protoshine::vote_releases(), vec![id]);
+        assert!(Protoshine::membership_approvals().is_empty());
+
+        System::set_block_number(2);
+        <Protoshine as OnInitialize<u64>>::on_initialize(2);
+        // the bond every legacy application reserved
+        assert_eq!(
+            Protoshine::membership_applications(id).unwrap(),
+            MembershipProposal {
+                who: 8,
+                stake_promised: 10,
+                shares_requested: 10,
+                bond: 1,
+                stage: ProposalStage::Passed,
+                time_proposed: 1,
+            }
+        );
+        assert_eq!(Protoshine::membership_approvals(), vec![id]);
+        assert_eq!(Protoshine::membership_application_nonce(&8), 1);
+
+        let open = Protoshine::membership_proposal_id(&9, 5, 5, 0);
+        assert_eq!(
+            Protoshine::membership_applications(open).unwrap().stage,
+            ProposalStage::Voting
+        );
+        assert_eq!(
+            Protoshine::vote_states(open).unwrap(),
+            VotingState {
+                in_favor: 8,
+                against: 0,
+                turnout: 8,
+                all_voters: 60,
+                electorate_weight: 60,
+                threshold: VoteThreshold::SimpleMajority,
+                weighting: VoteWeighting::Linear,
+            }
+        );
+        // voters are recovered from the members
+        assert_eq!(Protoshine::voters(open), vec![1, 2]);
+        assert_eq!(
+            Protoshine::votes_by_members(open, &2),
+            Some(Vote::InFavor(5))
+        );
+        assert!(
+            take_storage_value::<Vote>(b"Protoshine", b"VotesByMembers", &vote_key(2)).is_none()
+        );
+        assert_eq!(Protoshine::open_votes(), vec![open]);
+        assert_eq!(Protoshine::vote_expiries(5), vec![open]);
+
+        assert_eq!(Protoshine::storage_version(), Version::V4);
+        assert_eq!(Protoshine::share_checkpoints(&1), vec![(2, 10)]);
+        assert_eq!(Protoshine::vote_snapshot(open), Some(2));
+        assert_eq!(Protoshine::batch_period(), 2);
+
+        // closed votes release their shares and rejected proposals are pruned
+        let rejected = Protoshine::membership_proposal_id(&10, 5, 5, 0);
+        assert_eq!(Protoshine::vote_releases(), vec![id, rejected]);
+        <Protoshine as OnFinalize<u64>>::on_finalize(2);
+        assert!(Protoshine::vote_releases().is_empty());
+        assert_eq!(
+            Protoshine::membership_shares(&3).unwrap().reserved_shares,
+            0
+        );
+        assert!(Protoshine::membership_applications(rejected).is_none());
+        assert!(Protoshine::votes_by_members(rejected, &3).is_none());
+        // and the legacy approval is executed and pruned in the same block
+        assert_eq!(Protoshine::membership_shares(&8).unwrap().total_shares, 10);
+        assert!(Protoshine::membership_applications(id).is_none());
+
+        // the rebuilt tally can be changed like any other
+        assert_ok!(Protoshine::retract_vote(Origin::signed(2), open));
+        let state = Protoshine::vote_states(open).unwrap();
+        assert_eq!((state.in_favor, state.against, state.turnout), (3, 0, 3));
+    });
+}
+
+/// Apply for a grant and return the id of the new application
+fn apply_for_grant(who: u64, recipient: u64, amount: u64, schedule: GrantSchedule<u64>) -> H256 {
+    let nonce = Protoshine::grant_application_nonce(&who);
+    assert_ok!(Protoshine::grant_application(
+        Origin::signed(who),
+        recipient,
+        amount,
+        schedule.clone()
+    ));
+    Protoshine::grant_proposal_id(&who, &recipient, amount, &schedule, nonce)
+}
+
+#[test]
+fn grants_are_paid_out_of_the_bank_once_passed() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_noop!(
+            Protoshine::grant_application(Origin::signed(9), 7, 0, GrantSchedule::Immediate),
+            Error::<Test>::InvalidGrantApplication
+        );
+        let id = apply_for_grant(9, 7, 20, GrantSchedule::Immediate);
+        assert_eq!(ProposalKind::of::<Test>(id), Some(ProposalKind::Grant));
+        assert_eq!(Balances::reserved_balance(&9), 2);
+        sponsor_and_pass::<GrantProposalOf<Test>>(id);
+        // the bond is refunded on passage and the grant is due at the end of the block
+        assert_eq!(Balances::reserved_balance(&9), 0);
+        assert_eq!(Protoshine::recipients(1), vec![(id, 7, 20)]);
+
+        <Protoshine as OnFinalize<u64>>::on_finalize(1);
+        assert_eq!(Balances::free_balance(&7), 21);
+        assert_eq!(
+            Protoshine::grant_applications(id).unwrap().stage,
+            ProposalStage::Law
+        );
+        // the shares are unchanged so each is backed by less capital
+        let bank = Protoshine::bank_account();
+        assert_eq!(bank.shares, 60);
+        assert_eq!(Protoshine::bank_balance(bank).unwrap(), 40);
+
+        // the bank can't cover this one so it is retried after `BatchPeriod`
+        let id = apply_for_grant(9, 7, 50, GrantSchedule::Immediate);
+        sponsor_and_pass::<GrantProposalOf<Test>>(id);
+        <Protoshine as OnFinalize<u64>>::on_finalize(1);
+        assert_eq!(Balances::free_balance(&7), 21);
+        assert_eq!(Protoshine::recipients(3), vec![(id, 7, 50)]);
+        assert_eq!(
+            Protoshine::grant_applications(id).unwrap().stage,
+            ProposalStage::Passed
+        );
+    });
+}
+
+#[test]
+fn grants_are_decided_by_the_grant_quorum_when_voting_ends() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let id = apply_for_grant(9, 7, 20, GrantSchedule::Immediate);
+        assert_ok!(Protoshine::sponsor_proposal(
+            Origin::signed(1),
+            id,
+            VoteWeighting::Linear
+        ));
+        assert_ok!(Protoshine::vote_on_proposal(
+            Origin::signed(2),
+            id,
+            Vote::InFavor(5),
+            Conviction::None
+        ));
+        // nobody is against but the vote stays open until `VotingPeriod` ends
+        assert_eq!(
+            Protoshine::grant_applications(id).unwrap().stage,
+            ProposalStage::Voting
+        );
+
+        // 8 shares voted, short of the quorum of 12 (20% of 60)
+        <Protoshine as OnInitialize<u64>>::on_initialize(5);
+        assert_eq!(
+            Protoshine::grant_applications(id).unwrap().stage,
+            ProposalStage::Rejected
+        );
+        assert!(Protoshine::recipients(5).is_empty());
+        assert_eq!(Balances::reserved_balance(&9), 0);
+        assert_eq!(Balances::free_balance(&9), 16);
+    });
+}
+
+#[test]
+fn grant_installments_are_paid_every_period() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let schedule = GrantSchedule::Installments {
+            payments: 4,
+            period: 2,
+        };
+        let id = apply_for_grant(9, 10, 30, schedule);
+        sponsor_and_pass::<GrantProposalOf<Test>>(id);
+
+        // 30 / 4 = 7 per payment and the last one pays the remainder
+        for (block, paid) in vec![(1, 7), (3, 14), (5, 21)] {
+            System::set_block_number(block);
+            <Protoshine as OnFinalize<u64>>::on_finalize(block);
+            assert_eq!(Balances::free_balance(&10), 10 + paid);
+            assert_eq!(
+                Protoshine::grant_applications(id).unwrap().stage,
+                ProposalStage::Passed
+            );
+        }
+        assert_eq!(Protoshine::recipients(7), vec![(id, 10, 9)]);
+        System::set_block_number(7);
+        <Protoshine as OnFinalize<u64>>::on_finalize(7);
+        assert_eq!(Balances::free_balance(&10), 40);
+        // the votes were released before the last payment so the paid grant is pruned
+        assert!(Protoshine::grant_applications(id).is_none());
+        assert!(Protoshine::vote_states(id).is_none());
+        assert!(Protoshine::recipients(9).is_empty());
+        assert_eq!(
+            Protoshine::bank_balance(Protoshine::bank_account()).unwrap(),
+            30
+        );
+    });
+}
+
+#[test]
+fn meta_proposals_change_parameters_with_a_supermajority() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_eq!(Protoshine::batch_period(), 2);
+        assert_noop!(
+            Protoshine::meta_proposal(Origin::signed(8), ParameterChange::BatchPeriod(3)),
+            Error::<Test>::NotAMember
+        );
+        // changes that would leave some proposals unable to pass are rejected
+        for invalid in vec![
+            ParameterChange::MembershipProposalBond(0),
+            ParameterChange::MembershipSponsorBond(0),
+            ParameterChange::MembershipSponsorBond(11),
+            ParameterChange::MembershipVoteBond(0),
+            ParameterChange::MembershipVoteBond(Shares::max_value()),
+            ParameterChange::MaximumShareIssuance(Permill::zero()),
+            ParameterChange::MembershipConsensusThreshold(Permill::zero()),
+            ParameterChange::MembershipConsensusThreshold(Permill::from_percent(76)),
+            ParameterChange::BatchPeriod(0),
+        ] {
+            assert_noop!(
+                Protoshine::meta_proposal(Origin::signed(1), invalid),
+                Error::<Test>::InvalidParameterChange
+            );
+        }
+        assert_ok!(Protoshine::meta_proposal(
+            Origin::signed(1),
+            ParameterChange::MembershipSponsorBond(10)
+        ));
+        let change = ParameterChange::BatchPeriod(3);
+        assert_ok!(Protoshine::meta_proposal(Origin::signed(1), change.clone()));
+        let id = Protoshine::meta_proposal_id(&1, &change, 1);
+        assert_eq!(ProposalKind::of::<Test>(id), Some(ProposalKind::Meta));
+        // every meta proposal reserves a bond from the proposer
+        assert_eq!(Balances::reserved_balance(&1), 4);
+        assert_ok!(Protoshine::sponsor_proposal(
+            Origin::signed(1),
+            id,
+            VoteWeighting::Linear
+        ));
+
+        // the membership threshold is met but the quorum (30 of 60 shares) is not
+        for voter in 2..4 {
+            assert_ok!(Protoshine::vote_on_proposal(
+                Origin::signed(voter),
+                id,
+                Vote::InFavor(10),
+                Conviction::None
+            ));
+        }
+        assert_eq!(
+            Protoshine::meta_proposals(id).unwrap().stage,
+            ProposalStage::Voting
+        );
+        assert_eq!(Protoshine::batch_period(), 2);
+
+        // the quorum is met but the vote is only decided once its `VotingPeriod` ends
+        System::set_block_number(2);
+        assert_ok!(Protoshine::vote_on_proposal(
+            Origin::signed(4),
+            id,
+            Vote::InFavor(7),
+            Conviction::None
+        ));
+        assert_eq!(
+            Protoshine::meta_proposals(id).unwrap().stage,
+            ProposalStage::Voting
+        );
+        assert_eq!(Protoshine::batch_period(), 2);
+
+        System::set_block_number(5);
+        <Protoshine as OnInitialize<u64>>::on_initialize(5);
+        assert_eq!(
+            Protoshine::meta_proposals(id).unwrap().stage,
+            ProposalStage::Law
+        );
+        assert_eq!(Protoshine::batch_period(), 3);
+        assert_eq!(Protoshine::parameter_history(), vec![(5, change)]);
+        // the bond of the passed proposal is refunded
+        assert_eq!(Balances::reserved_balance(&1), 2);
+    });
+}
+
+#[test]
+fn meta_proposals_are_checked_again_when_they_pass() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        // every member holds 10 shares so a sponsor bond of 10 is valid when proposed
+        let change = ParameterChange::MembershipSponsorBond(10);
+        assert_ok!(Protoshine::meta_proposal(Origin::signed(1), change.clone()));
+        let id = Protoshine::meta_proposal_id(&1, &change, 0);
+        assert_ok!(Protoshine::sponsor_proposal(
+            Origin::signed(1),
+            id,
+            VoteWeighting::Linear
+        ));
+        for voter in 2..7 {
+            assert_ok!(Protoshine::vote_on_proposal(
+                Origin::signed(voter),
+                id,
+                Vote::InFavor(6),
+                Conviction::None
+            ));
+        }
+        // but no member holds 10 shares anymore once the vote ends
+        for member in 1..7 {
+            assert_ok!(Protoshine::leave_membership(Origin::signed(member), 1));
+        }
+        <Protoshine as OnInitialize<u64>>::on_initialize(5);
+        assert_eq!(
+            Protoshine::meta_proposals(id).unwrap().stage,
+            ProposalStage::Rejected
+        );
+        assert_eq!(Protoshine::membership_sponsor_bond(), 3);
+        assert!(Protoshine::parameter_history().is_empty());
+        assert!(protoshine_events().contains(&RawEvent::MetaProposalInvalidated(id, 33, 0)));
+        // the proposer isn't at fault so the bond is refunded
+        assert_eq!(Balances::reserved_balance(&1), 0);
     });
 }
 
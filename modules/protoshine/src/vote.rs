@@ -12,17 +12,20 @@ use super::*;
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
 #[non_exhaustive]
 /// Votes submitted by voting members
-/// - could add more explicit commands here like `change_vote` to remove ambiguity of #18
+/// - votes are changed by voting again (same kind adds, other kinds replace) and removed with
+/// `retract_vote`, see #18
 pub enum Vote {
     InFavor(Shares),
     Against(Shares),
+    /// Counts towards the turnout (and so the quorum) but towards neither direction
+    Abstain(Shares),
 }
 
 // not sure if this is necessary or if I can check the form another way
 impl Vote {
-    pub(crate) fn is_in_favor(&self) -> bool {
+    pub(crate) fn is_abstain(&self) -> bool {
         match self {
-            Vote::InFavor(_) => true,
+            Vote::Abstain(_) => true,
             _ => false,
         }
     }
@@ -30,6 +33,131 @@ impl Vote {
         match self {
             Vote::InFavor(shares) => *shares,
             Vote::Against(shares) => *shares,
+            Vote::Abstain(shares) => *shares,
+        }
+    }
+    /// The member's whole vote after voting `new` on top of `previous`
+    /// - a vote of the same kind (in favor, against or abstain) adds to the previous vote and
+    /// any other vote replaces it
+    pub(crate) fn combine(previous: Option<&Vote>, new: Vote) -> Result<Vote, VoteError> {
+        let add = |old: &Shares, shares: Shares| old.checked_add(shares).ok_or(VoteError::Overflow);
+        match (previous, new) {
+            (Some(Vote::InFavor(old)), Vote::InFavor(shares)) => {
+                Ok(Vote::InFavor(add(old, shares)?))
+            }
+            (Some(Vote::Against(old)), Vote::Against(shares)) => {
+                Ok(Vote::Against(add(old, shares)?))
+            }
+            (Some(Vote::Abstain(old)), Vote::Abstain(shares)) => {
+                Ok(Vote::Abstain(add(old, shares)?))
+            }
+            (_, new) => Ok(new),
+        }
+    }
+}
+
+/// Change in the shares a member reserves for their vote on a proposal
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum ShareDelta {
+    /// Reserve this many more shares
+    Reserve(Shares),
+    /// Release this many of the shares reserved
+    Release(Shares),
+}
+
+impl ShareDelta {
+    fn between(old: Shares, new: Shares) -> Self {
+        if new >= old {
+            ShareDelta::Reserve(new - old)
+        } else {
+            ShareDelta::Release(old - new)
+        }
+    }
+    /// Shares reserved once the change is applied to `reserved`
+    pub(crate) fn applied_to(self, reserved: Shares) -> Shares {
+        match self {
+            ShareDelta::Reserve(shares) => reserved.saturating_add(shares),
+            ShareDelta::Release(shares) => reserved.saturating_sub(shares),
+        }
+    }
+}
+
+/// Invalid transitions of a `VotingState`
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum VoteError {
+    /// A vote or tally would overflow `Shares`
+    Overflow,
+    /// The vote being replaced or retracted isn't part of the tally
+    NotInTally,
+    /// Changing a vote can't lower its conviction
+    ConvictionLowered,
+    /// Abstaining carries no weight so it can't carry conviction
+    ConvictionOnAbstain,
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Encode, Decode, RuntimeDebug)]
+/// Multiplies a vote's weight in exchange for keeping its reserved shares locked for
+/// `ConvictionLockPeriod`s after the proposal closes
+pub enum Conviction {
+    /// 1x weight, released as soon as the proposal closes
+    None,
+    /// 2x weight, locked for one period
+    Locked2x,
+    /// 3x weight, locked for two periods
+    Locked3x,
+    /// 4x weight, locked for three periods
+    Locked4x,
+}
+
+impl Default for Conviction {
+    fn default() -> Self {
+        Conviction::None
+    }
+}
+
+impl Conviction {
+    pub(crate) fn multiplier(self) -> Shares {
+        match self {
+            Conviction::None => 1,
+            Conviction::Locked2x => 2,
+            Conviction::Locked3x => 3,
+            Conviction::Locked4x => 4,
+        }
+    }
+    /// Number of `ConvictionLockPeriod`s the reserved shares stay locked after the proposal closes
+    pub(crate) fn lock_periods(self) -> u32 {
+        self.multiplier() - 1
+    }
+    /// Weight of a vote reserving `shares` with this conviction
+    pub(crate) fn weight(self, shares: Shares) -> Shares {
+        shares.saturating_mul(self.multiplier())
+    }
+}
+
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug)]
+/// How the shares reserved by a vote translate into its weight, chosen when the vote opens
+pub enum VoteWeighting {
+    /// Every share counts once
+    Linear,
+    /// A vote counts the integer square root of its shares
+    /// - weights are computed on each member's aggregated vote so splitting a vote into
+    /// increments doesn't add weight
+    Quadratic,
+}
+
+impl Default for VoteWeighting {
+    fn default() -> Self {
+        VoteWeighting::Linear
+    }
+}
+
+impl VoteWeighting {
+    pub(crate) fn weight(self, shares: Shares) -> Shares {
+        match self {
+            VoteWeighting::Linear => shares,
+            VoteWeighting::Quadratic => shares.integer_sqrt(),
         }
     }
 }
@@ -46,6 +174,16 @@ pub enum VoteThreshold {
     SuperMajorityAgainst,
     /// A simple majority of approvals is needed to pass this vote.
     SimpleMajority,
+    /// Turnout must reach `quorum` of all shares; at the quorum, `threshold` of the turnout must be
+    /// in favor and the required portion falls (down to a simple majority) as turnout rises.
+    /// - expressed as `in_favor >= threshold * quorum * electorate_weight` so the weight in favor
+    /// needed to pass is fixed by the quorum rather than by the turnout
+    TurnoutBiased { threshold: Permill, quorum: Permill },
+    /// Turnout must reach `quorum` of all shares and `threshold` of the turnout must be in favor,
+    /// which is never less than `threshold` of the quorum.
+    /// - at least as strict as `TurnoutBiased` with the same or a lower `threshold` and `quorum`
+    /// at every turnout, since its required portion of the turnout doesn't fall
+    QuorumConsensus { threshold: Permill, quorum: Permill },
     // SimpleBFT
     // unanimous approval
     // 1 approving member
@@ -64,18 +202,99 @@ pub trait Approved {
 /// The state of each proposal's ongoing voting
 /// - kept minimal to perform lazy computation to calculate if threshold requirements
 /// are met at any time
-pub struct MembershipVotingState {
-    /// Total shares in favor
+pub struct VotingState {
+    /// Total weight in favor
     pub in_favor: Shares,
-    /// Total shares against
+    /// Total weight against
     pub against: Shares,
-    /// All shares that can vote (TODO: add voter registration for more friction, configuration at least?)
+    /// Total shares reserved by votes, regardless of their conviction
+    pub turnout: Shares,
+    /// All shares that can vote, equal to the bank's shares while the vote is open
+    /// (TODO: add voter registration for more friction, configuration at least?)
     pub all_voters: Shares,
+    /// Weight of `all_voters` under `weighting` if every member voted all of their shares
+    /// without conviction, kept up to date with `all_voters`
+    /// - the weight in favor required at the quorum is a portion of this rather than of
+    /// `all_voters`, so that quadratic votes aren't held to a linear requirement
+    pub electorate_weight: Shares,
     /// Threshold for vote passage
     pub threshold: VoteThreshold,
+    /// How shares translate into `in_favor` and `against` weight
+    pub weighting: VoteWeighting,
 }
 
-impl Approved for MembershipVotingState {
+impl VotingState {
+    /// Weight of a member's (aggregated) vote reserving `shares` with `conviction`
+    pub(crate) fn weight(&self, conviction: Conviction, shares: Shares) -> Shares {
+        conviction.weight(self.weighting.weight(shares))
+    }
+
+    /// Tally a member's `new` vote and conviction on top of their `previous` vote and conviction
+    /// - see `Vote::combine` for how `new` is merged with `previous`; the weighting and conviction
+    /// apply to the whole vote so splitting a vote into increments doesn't change its weight
+    /// - returns the new state and the change in the shares reserved by the member's vote
+    pub fn apply(
+        &self,
+        previous: Option<(Vote, Conviction)>,
+        new: (Vote, Conviction),
+    ) -> Result<(Self, ShareDelta), VoteError> {
+        let (new, conviction) = new;
+        if new.is_abstain() && conviction != Conviction::None {
+            return Err(VoteError::ConvictionOnAbstain);
+        }
+        let mut state = self.clone();
+        let old_shares = match &previous {
+            Some((old, old_conviction)) => {
+                if conviction < *old_conviction {
+                    return Err(VoteError::ConvictionLowered);
+                }
+                state.subtract(old, *old_conviction)?;
+                old.inner()
+            }
+            None => 0,
+        };
+        let vote = Vote::combine(previous.as_ref().map(|(old, _)| old), new)?;
+        state.add(&vote, conviction)?;
+        Ok((state, ShareDelta::between(old_shares, vote.inner())))
+    }
+
+    /// Remove a member's whole vote from the tally
+    /// - returns the new state and the shares released by the member's vote
+    pub fn retract(&self, previous: (Vote, Conviction)) -> Result<(Self, ShareDelta), VoteError> {
+        let (old, old_conviction) = previous;
+        let mut state = self.clone();
+        state.subtract(&old, old_conviction)?;
+        Ok((state, ShareDelta::Release(old.inner())))
+    }
+
+    fn add(&mut self, vote: &Vote, conviction: Conviction) -> Result<(), VoteError> {
+        let weight = self.weight(conviction, vote.inner());
+        let add =
+            |tally: Shares, shares: Shares| tally.checked_add(shares).ok_or(VoteError::Overflow);
+        match vote {
+            Vote::InFavor(_) => self.in_favor = add(self.in_favor, weight)?,
+            Vote::Against(_) => self.against = add(self.against, weight)?,
+            Vote::Abstain(_) => (),
+        }
+        self.turnout = add(self.turnout, vote.inner())?;
+        Ok(())
+    }
+
+    fn subtract(&mut self, vote: &Vote, conviction: Conviction) -> Result<(), VoteError> {
+        let weight = self.weight(conviction, vote.inner());
+        let sub =
+            |tally: Shares, shares: Shares| tally.checked_sub(shares).ok_or(VoteError::NotInTally);
+        match vote {
+            Vote::InFavor(_) => self.in_favor = sub(self.in_favor, weight)?,
+            Vote::Against(_) => self.against = sub(self.against, weight)?,
+            Vote::Abstain(_) => (),
+        }
+        self.turnout = sub(self.turnout, vote.inner())?;
+        Ok(())
+    }
+}
+
+impl Approved for VotingState {
     /// Given `approve` votes for and `against` votes against from a total electorate size of
     /// `electorate` of whom `voters` voted (`electorate - voters` are abstainers) then returns true if the
     /// overall outcome is in favor of approval.
@@ -83,24 +302,64 @@ impl Approved for MembershipVotingState {
     /// We assume each *voter* may cast more than one *vote*, hence `voters` is not necessarily equal to
     /// `approve + against`.
     fn approved(&self) -> bool {
-        let total_voters = self.in_favor + self.against;
+        // conviction multiplies the weights so their sum may not fit in `Shares`
+        let (in_favor, against) = (u64::from(self.in_favor), u64::from(self.against));
+        let total_voters = in_favor + against;
         let sqrt_voters = total_voters.integer_sqrt();
-        let sqrt_electorate = self.all_voters.integer_sqrt();
+        let sqrt_electorate = u64::from(self.all_voters).integer_sqrt();
         if sqrt_voters.is_zero() {
             return false;
         }
         match self.threshold {
             VoteThreshold::SuperMajorityApprove => {
-                compare_rationals(self.against, sqrt_voters, self.in_favor, sqrt_electorate)
+                compare_rationals(against, sqrt_voters, in_favor, sqrt_electorate)
             }
             VoteThreshold::SuperMajorityAgainst => {
-                compare_rationals(self.against, sqrt_electorate, self.in_favor, sqrt_voters)
+                compare_rationals(against, sqrt_electorate, in_favor, sqrt_voters)
+            }
+            VoteThreshold::SimpleMajority => in_favor > against,
+            VoteThreshold::TurnoutBiased { threshold, quorum } => {
+                let one = Permill::one().deconstruct() as u128;
+                let required = threshold.deconstruct() as u128
+                    * quorum.deconstruct() as u128
+                    * self.electorate_weight as u128;
+                is_at_least(self.turnout.into(), quorum, self.all_voters.into())
+                    && in_favor > against
+                    && in_favor as u128 * one * one >= required
+            }
+            VoteThreshold::QuorumConsensus { threshold, quorum } => {
+                let one = Permill::one().deconstruct() as u128;
+                let required = threshold.deconstruct() as u128
+                    * quorum.deconstruct() as u128
+                    * self.electorate_weight as u128;
+                is_at_least(self.turnout.into(), quorum, self.all_voters.into())
+                    && is_at_least(in_favor, threshold, total_voters)
+                    && in_favor as u128 * one * one >= required
             }
-            VoteThreshold::SimpleMajority => self.in_favor > self.against,
         }
     }
 }
 
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+/// Voting weight lent by a member to another member
+/// - the delegated shares are owned by the delegator and are only reserved on the delegator's
+/// `ShareProfile` while the delegate's votes use them
+pub struct Delegation<AccountId> {
+    /// The member that can vote with the delegated shares
+    pub delegate: AccountId,
+    /// Maximum shares the delegate can draw at any time
+    pub shares: Shares,
+    /// Shares currently reserved for the delegate's votes
+    pub drawn: Shares,
+}
+
+/// Return `true` iff `part >= ratio * whole` (computed exactly rather than with `Permill`'s rounding)
+fn is_at_least(part: u64, ratio: Permill, whole: u64) -> bool {
+    part as u128 * Permill::one().deconstruct() as u128
+        >= ratio.deconstruct() as u128 * whole as u128
+}
+
 /// Return `true` iff `n1 / d1 < n2 / d2`. `d1` and `d2` may not be zero.
 fn compare_rationals<
     T: Zero + Mul<T, Output = T> + Div<T, Output = T> + Rem<T, Output = T> + Ord + Copy,
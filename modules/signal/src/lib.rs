@@ -2,6 +2,8 @@ use codec::FullCodec;
 use sp_runtime::traits::{MaybeSerializeDeserialize, SimpleArithmetic};
 use sp_std::fmt::Debug;
 
+pub mod versioning;
+
 /// Captures the minimal required behavior for the `Bank` abstraction with
 /// respect to share issuance
 /// - WARNING: constraints must be satisfied around the logic that calls these
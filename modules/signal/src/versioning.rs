@@ -1,8 +1,24 @@
 //! versioning
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
 
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug)]
 #[non_exhaustive]
 pub enum Version {
     /// TODO: could make this a wrapper around a semver type?
     /// - look into ontology rlay project
     V1,
-}
\ No newline at end of file
+    /// Proposals are keyed by hashes instead of `u32` indices
+    V2,
+    /// Members' share totals are checkpointed so votes can use a snapshot
+    V3,
+    /// Economic parameters are storage values that meta proposals can change
+    V4,
+}
+
+/// Storage that predates versioning is `V1`
+impl Default for Version {
+    fn default() -> Self {
+        Version::V1
+    }
+}